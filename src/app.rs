@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use clap::{ArgAction, Parser, ValueHint};
+use clap::{ArgAction, Args, Parser, Subcommand, ValueHint};
 
 #[derive(Debug, Parser)]
 #[command(about, version, args_override_self = true, disable_help_flag = true)]
@@ -44,7 +44,7 @@ pub struct Cli {
     #[arg(long, value_name = "PATH")]
     pub config_file: Option<PathBuf>,
 
-    /// Display one entry per line
+    /// Display one entry per line, sorted once the whole listing is read unless -U is given
     #[arg(short = '1', long)]
     pub oneline: bool,
 
@@ -80,6 +80,14 @@ pub struct Cli {
     #[arg(long)]
     pub total_size: bool,
 
+    /// Stop walking directories for their total size after reaching specified depth
+    #[arg(long, value_name = "NUM", requires = "total_size")]
+    pub total_size_depth: Option<usize>,
+
+    /// Stop walking directories for their total size after the given number of seconds
+    #[arg(long, value_name = "SECONDS", requires = "total_size")]
+    pub total_size_timeout: Option<u64>,
+
     /// How to display date [default: date] [possible values: date, relative, +date-time-format]
     #[arg(long, value_parser = validate_date_argument)]
     pub date: Option<String>,
@@ -100,16 +108,21 @@ pub struct Cli {
     #[arg(short = 'v', long)]
     pub versionsort: bool,
 
-    /// Sort by TYPE instead of name
+    /// Sort by TYPEs instead of name, e.g. content-type,-time. A TYPE prefixed with - is sorted
+    /// in reverse [possible values: size, time, version, extension, name, content-type, etag,
+    /// path, none]
     #[arg(
         long,
         value_name = "TYPE",
-        value_parser = ["size", "time", "version", "extension", "none"],
+        action = ArgAction::Set,
+        value_delimiter = ',',
+        value_parser = validate_sort_key,
+        allow_hyphen_values = true,
         overrides_with_all = ["timesort", "sizesort", "extensionsort", "versionsort", "no_sort"]
     )]
-    pub sort: Option<String>,
+    pub sort: Vec<String>,
 
-    /// Do not sort. List entries in directory order
+    /// Do not sort. List entries in directory order, printing them as soon as they are listed
     #[arg(short = 'U', long, overrides_with_all = ["timesort", "sizesort", "extensionsort", "versionsort", "sort"])]
     pub no_sort: bool,
 
@@ -129,7 +142,10 @@ pub struct Cli {
     #[arg(
         long,
         value_delimiter = ',',
-        value_parser = ["permission", "user", "group", "context", "size", "date", "name", "inode", "links"],
+        value_parser = [
+            "permission", "user", "group", "size", "date", "name", "inode", "links",
+            "content-type", "etag", "content-md5", "content-disposition",
+        ],
     )]
     pub blocks: Vec<String>,
 
@@ -146,6 +162,22 @@ pub struct Cli {
     #[arg(short = 'I', long, value_name = "PATTERN")]
     pub ignore_glob: Vec<String>,
 
+    /// Only display the files larger than SIZE, like 42, 1.5MB or 3G
+    #[arg(long, value_name = "SIZE")]
+    pub larger: Option<String>,
+
+    /// Only display the files smaller than SIZE, like 42, 1.5MB or 3G
+    #[arg(long, value_name = "SIZE")]
+    pub smaller: Option<String>,
+
+    /// Only display the files modified after DATE, like 2026-01-01, or DURATION ago, like 3d
+    #[arg(long, value_name = "DATE|DURATION")]
+    pub newer: Option<String>,
+
+    /// Only display the files modified before DATE, like 2026-01-01, or DURATION ago, like 3d
+    #[arg(long, value_name = "DATE|DURATION")]
+    pub older: Option<String>,
+
     /// Display the index number of each file
     #[arg(short, long)]
     pub inode: bool,
@@ -163,18 +195,298 @@ pub struct Cli {
     #[arg(long, value_name = "MODE", value_parser = ["always", "auto", "never"])]
     pub hyperlink: Option<String>,
 
+    /// How to print the entries [default: text]
+    #[arg(long, value_name = "FORMAT", value_parser = ["text", "json", "ndjson", "csv", "tsv"])]
+    pub format: Option<String>,
+
     /// Display block headers
     #[arg(long)]
     pub header: bool,
 
+    /// Number of requests sent to the storage at the same time [default: 16]
+    #[arg(
+        long,
+        value_name = "NUM",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    pub jobs: Option<usize>,
+
+    /// Endpoint of the S3 compatible service, like MinIO, Ceph or R2
+    #[arg(long, value_name = "URL")]
+    pub endpoint: Option<String>,
+
+    /// Region of the S3 buckets [default: detected from the bucket]
+    #[arg(long, value_name = "REGION")]
+    pub region: Option<String>,
+
+    /// Address the S3 buckets in the path rather than in the host name
+    #[arg(long)]
+    pub path_style: bool,
+
+    /// List public S3 buckets without signing the requests
+    #[arg(long, conflicts_with = "aws_profile")]
+    pub anonymous: bool,
+
+    /// Read the S3 credential from this profile of ~/.aws/credentials
+    #[arg(long, value_name = "NAME")]
+    pub aws_profile: Option<String>,
+
     /// Includes files with the windows system protection flag set.
     /// This is the same as --all on other platforms
     #[arg(long, hide = !cfg!(windows))]
     pub system_protected: bool,
 
+    /// Write the entries of a YAML fixture into memory:// before listing, for the tests. Only
+    /// in the debug builds, the release binaries do not have it
+    #[cfg(debug_assertions)]
+    #[arg(long, value_name = "FIXTURE", hide = true)]
+    pub seed: Option<PathBuf>,
+
     /// Print help information
     #[arg(long, action = ArgAction::Help)]
     help: (),
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Print the content of files to the standard output
+    Cat(CatArgs),
+    /// Copy files between any two storages
+    Cp(CpArgs),
+    /// Summarise the sizes and the number of files under directories and prefixes
+    Du(DuArgs),
+    /// Print the files under directories and prefixes matching all the given predicates
+    Find(FindArgs),
+    /// Remove files, or whole directories and prefixes
+    Rm(RmArgs),
+    /// Print all the metadata the storage returns for files
+    Stat(StatArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct CatArgs {
+    #[arg(value_name = "FILE", required = true, value_hint = ValueHint::AnyPath)]
+    pub inputs: Vec<PathBuf>,
+
+    /// Only print a range of bytes: START-END with both ends included, START- up to the end,
+    /// or -LENGTH for the last bytes
+    #[arg(long, value_name = "START-END", value_parser = parse_byte_range, allow_hyphen_values = true)]
+    pub range: Option<ByteRange>,
+}
+
+#[derive(Debug, Args)]
+pub struct CpArgs {
+    #[arg(value_name = "SOURCE", required = true, value_hint = ValueHint::AnyPath)]
+    pub sources: Vec<PathBuf>,
+
+    #[arg(value_name = "DEST", required = true, value_hint = ValueHint::AnyPath)]
+    pub destination: PathBuf,
+
+    /// Copy directories recursively
+    #[arg(short, long)]
+    pub recursive: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct DuArgs {
+    #[arg(value_name = "FILE", default_value = ".", value_hint = ValueHint::AnyPath)]
+    pub inputs: Vec<PathBuf>,
+
+    /// Print the directories down to the given depth, the input itself is at depth 0
+    #[arg(short, long, value_name = "NUM")]
+    pub depth: Option<usize>,
+
+    /// Only print the total of each input
+    #[arg(short, long, conflicts_with = "depth")]
+    pub summarize: bool,
+
+    /// Sort the directories by path or from the largest to the smallest
+    #[arg(long, value_name = "TYPE", value_parser = ["path", "size"], default_value = "path")]
+    pub sort: String,
+
+    /// How to display size [default: default]
+    #[arg(long, value_name = "MODE", value_parser = ["default", "short", "bytes"])]
+    pub size: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct FindArgs {
+    #[arg(value_name = "PATH", default_value = ".", value_hint = ValueHint::AnyPath)]
+    pub inputs: Vec<PathBuf>,
+
+    /// Only the files with a name matching the glob pattern
+    #[arg(long, value_name = "PATTERN")]
+    pub name: Option<String>,
+
+    /// Like --name, but the match is case insensitive
+    #[arg(long, value_name = "PATTERN", conflicts_with = "name")]
+    pub iname: Option<String>,
+
+    /// Only the files with a size over (+N), under (-N) or of N units, rounded up. The unit is
+    /// the suffix: c for bytes (default), k for KiB, M for MiB, G for GiB and T for TiB
+    #[arg(long, value_name = "[+-]N[ckMGT]", value_parser = parse_size_predicate, allow_hyphen_values = true)]
+    pub size: Option<SizePredicate>,
+
+    /// Only the files modified after the date, like 2026-01-01 or 2026-01-01T12:00:00Z, or the
+    /// duration ago, like 3d
+    #[arg(long, value_name = "DATE|DURATION")]
+    pub newer: Option<String>,
+
+    /// Only the files modified over (+N), under (-N) or exactly N days ago
+    #[arg(long, value_name = "[+-]N", value_parser = parse_comparison, allow_hyphen_values = true)]
+    pub mtime: Option<Comparison>,
+
+    /// Only the files of the type: f for regular files, d for directories, l for symbolic links
+    #[arg(long = "type", value_name = "TYPE", value_parser = ["f", "d", "l"])]
+    pub file_type: Option<String>,
+
+    /// Descend at most the given number of levels under the inputs
+    #[arg(long, value_name = "NUM")]
+    pub maxdepth: Option<usize>,
+
+    /// Print the paths followed by a NUL character, instead of the rendered names
+    #[arg(long)]
+    pub print0: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct RmArgs {
+    #[arg(value_name = "FILE", required = true, value_hint = ValueHint::AnyPath)]
+    pub inputs: Vec<PathBuf>,
+
+    /// Remove directories and their content recursively
+    #[arg(short, long)]
+    pub recursive: bool,
+
+    /// Only remove the files with paths matching the glob pattern(s).
+    /// More than one can be specified by repeating the argument
+    #[arg(long, value_name = "PATTERN")]
+    pub include: Vec<String>,
+
+    /// Keep the files with paths matching the glob pattern(s).
+    /// More than one can be specified by repeating the argument
+    #[arg(long, value_name = "PATTERN")]
+    pub exclude: Vec<String>,
+
+    /// Print what would be removed, without removing anything
+    #[arg(short = 'n', long)]
+    pub dry_run: bool,
+
+    /// Do not ask for a confirmation
+    #[arg(short, long)]
+    pub yes: bool,
+
+    /// Ask for a confirmation when more objects than this would be removed
+    #[arg(long, value_name = "NUM", default_value_t = 10)]
+    pub confirm_over: usize,
+}
+
+#[derive(Debug, Args)]
+pub struct StatArgs {
+    #[arg(value_name = "FILE", required = true, value_hint = ValueHint::AnyPath)]
+    pub inputs: Vec<PathBuf>,
+
+    /// Print one JSON object per file
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// A range of bytes, written like in the HTTP `Range` header.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ByteRange {
+    /// The bytes from the first offset to the second one, both included.
+    Bounded(u64, u64),
+    /// The bytes from the offset up to the end.
+    From(u64),
+    /// The given number of bytes at the end.
+    Suffix(u64),
+}
+
+/// A number compared like in `find`: over the value with `+N`, under it with `-N`, or equal
+/// to it with `N`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Comparison {
+    /// How the compared number is ordered against the `value`.
+    pub ordering: std::cmp::Ordering,
+    pub value: u64,
+}
+
+/// A size compared with a [Comparison] of a number of `unit` bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SizePredicate {
+    pub comparison: Comparison,
+    pub unit: u64,
+}
+
+fn parse_comparison(arg: &str) -> Result<Comparison, String> {
+    let (ordering, value) = if let Some(value) = arg.strip_prefix('+') {
+        (std::cmp::Ordering::Greater, value)
+    } else if let Some(value) = arg.strip_prefix('-') {
+        (std::cmp::Ordering::Less, value)
+    } else {
+        (std::cmp::Ordering::Equal, arg)
+    };
+    let value = value
+        .parse::<u64>()
+        .map_err(|_| format!("invalid number {arg}, expected +N, -N or N"))?;
+    Ok(Comparison { ordering, value })
+}
+
+fn parse_size_predicate(arg: &str) -> Result<SizePredicate, String> {
+    let (number, unit) = match arg.chars().last() {
+        Some('c') => (&arg[..arg.len() - 1], 1),
+        Some('k') => (&arg[..arg.len() - 1], 1 << 10),
+        Some('M') => (&arg[..arg.len() - 1], 1 << 20),
+        Some('G') => (&arg[..arg.len() - 1], 1 << 30),
+        Some('T') => (&arg[..arg.len() - 1], 1 << 40),
+        _ => (arg, 1),
+    };
+    let comparison = parse_comparison(number)
+        .map_err(|_| format!("invalid size {arg}, expected [+-]N[ckMGT]"))?;
+    Ok(SizePredicate { comparison, unit })
+}
+
+fn parse_byte_range(arg: &str) -> Result<ByteRange, String> {
+    let invalid = || format!("invalid range {arg}, expected START-END, START- or -LENGTH");
+    let (start, end) = arg.split_once('-').ok_or_else(invalid)?;
+    let parse = |n: &str| n.parse::<u64>().map_err(|_| invalid());
+    match (start, end) {
+        ("", "") => Err(invalid()),
+        ("", length) => match parse(length)? {
+            0 => Err(invalid()),
+            length => Ok(ByteRange::Suffix(length)),
+        },
+        (start, "") => Ok(ByteRange::From(parse(start)?)),
+        (start, end) => {
+            let (start, end) = (parse(start)?, parse(end)?);
+            if start > end {
+                return Err(format!("invalid range {arg}, START is after END"));
+            }
+            Ok(ByteRange::Bounded(start, end))
+        }
+    }
+}
+
+fn validate_sort_key(arg: &str) -> Result<String, String> {
+    const COLUMNS: [&str; 9] = [
+        "size",
+        "time",
+        "version",
+        "extension",
+        "name",
+        "content-type",
+        "etag",
+        "path",
+        "none",
+    ];
+    if COLUMNS.contains(&arg.strip_prefix('-').unwrap_or(arg)) {
+        Ok(arg.to_owned())
+    } else {
+        Err(format!("possible values: {}", COLUMNS.join(", ")))
+    }
 }
 
 fn validate_date_argument(arg: &str) -> Result<String, String> {
@@ -61,6 +61,12 @@ pub enum Elem {
     },
 
     TreeEdge,
+
+    /// Object metadata
+    ContentType,
+    Etag,
+    ContentMd5,
+    ContentDisposition,
 }
 
 impl Elem {
@@ -121,6 +127,10 @@ impl Elem {
             Elem::TreeEdge => theme.tree_edge,
             Elem::Links { valid: false } => theme.links.invalid,
             Elem::Links { valid: true } => theme.links.valid,
+            Elem::ContentType => theme.metadata.content_type,
+            Elem::Etag => theme.metadata.etag,
+            Elem::ContentMd5 => theme.metadata.content_md5,
+            Elem::ContentDisposition => theme.metadata.content_disposition,
         }
     }
 }
@@ -388,6 +398,12 @@ mod elem {
                 valid: Color::AnsiValue(13),    // Pink
                 invalid: Color::AnsiValue(245), // Grey
             },
+            metadata: color::Metadata {
+                content_type: Color::AnsiValue(110),        // LightSkyBlue3
+                etag: Color::AnsiValue(245),                // Grey
+                content_md5: Color::AnsiValue(245),         // Grey
+                content_disposition: Color::AnsiValue(187), // LightYellow3
+            },
             tree_edge: Color::AnsiValue(245), // Grey
         }
     }
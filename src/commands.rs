@@ -0,0 +1,8 @@
+//! The subcommands of `dl`, besides listing.
+
+pub mod cat;
+pub mod cp;
+pub mod du;
+pub mod find;
+pub mod rm;
+pub mod stat;
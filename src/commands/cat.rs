@@ -0,0 +1,149 @@
+//! This module implements `dl cat`, which streams the content of files to the standard output.
+
+use crate::app::CatArgs;
+use crate::config_file::Config;
+use crate::dal::{Uri, DAL};
+use crate::{print_error, ExitCode};
+
+use futures::{AsyncRead, AsyncReadExt};
+
+use std::io::{self, Write};
+use std::path::Path;
+
+const BUFFER_SIZE: usize = 64 * 1024;
+
+/// Print the content of each input, one after the other.
+///
+/// An input which can not be read is reported and skipped, the following ones are still printed.
+pub async fn run(args: &CatArgs, config: &Config, concurrency: usize) -> ExitCode {
+    let work_dir = match std::env::current_dir() {
+        Ok(dir) => dir,
+        Err(err) => {
+            print_error!("current directory: {}.", err);
+            return ExitCode::MajorIssue;
+        }
+    };
+
+    let mut exit_code = ExitCode::OK;
+    for input in &args.inputs {
+        if let Err(err) = cat(&work_dir, input, args, config, concurrency).await {
+            print_error!("{}: {}.", input.display(), err);
+            exit_code.set_if_greater(ExitCode::MajorIssue);
+        }
+    }
+    exit_code
+}
+
+async fn cat(
+    work_dir: &Path,
+    input: &Path,
+    args: &CatArgs,
+    config: &Config,
+    concurrency: usize,
+) -> io::Result<()> {
+    let uri = Uri::parse(input, config)?;
+    let dal = DAL::new(work_dir, &uri, concurrency)?;
+    let reader = dal.reader(&uri.path, args.range).await?;
+
+    let stdout = io::stdout();
+    if let Err(err) = copy(reader, &mut stdout.lock()).await {
+        // The reader has failed, or stdout has been closed before the end, like with
+        // `dl cat file | head`, then we stop with success, as `print_output` does.
+        if err.kind() == io::ErrorKind::BrokenPipe {
+            std::process::exit(0);
+        }
+        return Err(err);
+    }
+    Ok(())
+}
+
+/// Copy everything from `reader` to `out`, one buffer at a time.
+async fn copy<R, W>(mut reader: R, out: &mut W) -> io::Result<u64>
+where
+    R: AsyncRead + Unpin,
+    W: Write,
+{
+    let mut buf = vec![0; BUFFER_SIZE];
+    let mut total = 0;
+    loop {
+        let n = reader.read(&mut buf).await?;
+        if n == 0 {
+            out.flush()?;
+            return Ok(total);
+        }
+        out.write_all(&buf[..n])?;
+        total += n as u64;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::copy;
+
+    use crate::app::{ByteRange, Cli, Command};
+    use crate::config_file::Config;
+    use crate::dal::{Uri, DAL};
+
+    use assert_fs::prelude::*;
+    use clap::Parser;
+    use std::path::Path;
+
+    fn range_of(argv: &[&str]) -> Option<ByteRange> {
+        match Cli::try_parse_from(argv).unwrap().command {
+            Some(Command::Cat(args)) => args.range,
+            _ => panic!("not a cat command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(None, range_of(&["lsd", "cat", "file"]));
+        assert_eq!(
+            Some(ByteRange::Bounded(2, 5)),
+            range_of(&["lsd", "cat", "--range", "2-5", "file"])
+        );
+        assert_eq!(
+            Some(ByteRange::From(2)),
+            range_of(&["lsd", "cat", "--range", "2-", "file"])
+        );
+        assert_eq!(
+            Some(ByteRange::Suffix(3)),
+            range_of(&["lsd", "cat", "--range", "-3", "file"])
+        );
+    }
+
+    #[test]
+    fn test_parse_range_invalid() {
+        for range in ["5-2", "-", "a-b", "-0", "12"] {
+            let argv = ["lsd", "cat", "--range", range, "file"];
+            assert!(Cli::try_parse_from(argv).is_err(), "{range}");
+        }
+    }
+
+    #[test]
+    fn test_cat_requires_input() {
+        assert!(Cli::try_parse_from(["lsd", "cat"]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_copy_range() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let file = dir.child("file");
+        file.write_str("0123456789").unwrap();
+
+        let uri = Uri::parse(file.path(), &Config::with_none()).unwrap();
+        let dal = DAL::new(Path::new("/"), &uri, 4).unwrap();
+        for (range, expected) in [
+            (None, "0123456789"),
+            (Some(ByteRange::Bounded(2, 5)), "2345"),
+            (Some(ByteRange::From(7)), "789"),
+            (Some(ByteRange::Suffix(3)), "789"),
+            (Some(ByteRange::Suffix(20)), "0123456789"),
+        ] {
+            let reader = dal.reader(&uri.path, range).await.unwrap();
+            let mut out = vec![];
+            copy(reader, &mut out).await.unwrap();
+            assert_eq!(expected, String::from_utf8(out).unwrap());
+        }
+    }
+}
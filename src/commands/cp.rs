@@ -0,0 +1,472 @@
+//! This module implements `dl cp`, which copies files between any two storages.
+//!
+//! Every file goes through `dl`: it is read from the [DAL] of the source and written to the
+//! [DAL] of the destination, as OpenDAL has no copy operation yet, even within a service.
+
+use crate::app::CpArgs;
+use crate::config_file::Config;
+use crate::dal::{listed_path, Uri, DAL};
+use crate::flags::{Display, Flags};
+use crate::meta::{FileType, Size};
+use crate::{print_error, print_output, ExitCode};
+
+use futures::{AsyncRead, AsyncReadExt};
+
+use std::io::{self, Error, ErrorKind};
+use std::path::{Path, PathBuf};
+
+/// The size of the parts the files are streamed by. A file which fits in one part is written
+/// in one request, the multipart uploads of object stores need parts of at least 5 MiB.
+const BUFFER_SIZE: usize = 8 * 1024 * 1024;
+
+/// The files to copy from one source.
+struct Plan {
+    from: DAL,
+    files: Vec<Job>,
+    /// Whether some directories of the source could not be listed.
+    exit_code: ExitCode,
+}
+
+/// One file to copy, with its paths in the services and as shown to the user.
+struct Job {
+    src: PathBuf,
+    dst: PathBuf,
+    shown_src: PathBuf,
+    shown_dst: PathBuf,
+}
+
+#[derive(Default)]
+struct Summary {
+    files: u64,
+    bytes: u64,
+    failed: u64,
+}
+
+/// Copy each source to the destination, printing every copied file and a final summary.
+///
+/// A source which can not be copied at all is a major issue, a file which fails to be copied
+/// from a directory is a minor one, the other files are still copied.
+pub async fn run(args: &CpArgs, config: &Config, flags: &Flags) -> ExitCode {
+    let work_dir = match std::env::current_dir() {
+        Ok(dir) => dir,
+        Err(err) => {
+            print_error!("current directory: {}.", err);
+            return ExitCode::MajorIssue;
+        }
+    };
+
+    let (dest, to) = match open(&work_dir, &args.destination, config, flags) {
+        Ok(opened) => opened,
+        Err(err) => {
+            print_error!("{}: {}.", args.destination.display(), err);
+            return ExitCode::MajorIssue;
+        }
+    };
+    let into_dir = args.sources.len() > 1
+        || args.destination.to_string_lossy().ends_with('/')
+        || to.is_dir(&dest.path).await;
+
+    let mut exit_code = ExitCode::OK;
+    let mut summary = Summary::default();
+    for source in &args.sources {
+        let plan = match plan(&work_dir, source, &dest, into_dir, args, config, flags).await {
+            Ok(plan) => plan,
+            Err(err) => {
+                print_error!("{}: {}.", source.display(), err);
+                exit_code.set_if_greater(ExitCode::MajorIssue);
+                continue;
+            }
+        };
+
+        exit_code.set_if_greater(plan.exit_code);
+        for job in &plan.files {
+            match copy(&plan.from, &job.src, &to, &job.dst).await {
+                Ok(size) => {
+                    print_output!(
+                        "{} -> {} ({})\n",
+                        job.shown_src.display(),
+                        job.shown_dst.display(),
+                        human(size, flags)
+                    );
+                    summary.files += 1;
+                    summary.bytes += size;
+                }
+                Err(err) => {
+                    print_error!("{}: {}.", job.shown_src.display(), err);
+                    summary.failed += 1;
+                    exit_code.set_if_greater(ExitCode::MinorIssue);
+                }
+            }
+        }
+    }
+
+    print_output!(
+        "{} copied ({}), {} failed\n",
+        plural(summary.files, "file"),
+        human(summary.bytes, flags),
+        summary.failed
+    );
+    exit_code
+}
+
+/// List the files to copy from `source`, and where each of them goes in `destination`.
+async fn plan(
+    work_dir: &Path,
+    source: &Path,
+    destination: &Uri,
+    into_dir: bool,
+    args: &CpArgs,
+    config: &Config,
+    flags: &Flags,
+) -> io::Result<Plan> {
+    let (uri, from) = open(work_dir, source, config, flags)?;
+    let meta = from.from_path(&uri.path).await?;
+    let name = meta.name().file_name().to_string();
+    let (target, shown_target) = if into_dir {
+        (destination.path.join(&name), args.destination.join(&name))
+    } else {
+        (destination.path.clone(), args.destination.clone())
+    };
+
+    if !meta.file_type().is_dirlike() {
+        let job = Job {
+            src: uri.path,
+            dst: target,
+            shown_src: source.to_path_buf(),
+            shown_dst: shown_target,
+        };
+        return Ok(Plan {
+            from,
+            files: vec![job],
+            exit_code: ExitCode::OK,
+        });
+    }
+    if !args.recursive {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "is a directory, use -r to copy it",
+        ));
+    }
+
+    let base = listed_path(&uri.path);
+    let mut files = vec![];
+    let mut outside = None;
+    // Everything is copied, whatever the display flags and the filters of the listings.
+    let flags = Flags {
+        concurrency: flags.concurrency,
+        display: Display::AlmostAll,
+        ..Flags::default()
+    };
+    let exit_code = from
+        .walk(&meta, usize::MAX, &flags, |_, entry| {
+            if matches!(entry.file_type(), FileType::Directory { .. }) {
+                return;
+            }
+            match entry.path.strip_prefix(&base) {
+                Ok(relative) => files.push(Job {
+                    src: entry.path.clone(),
+                    dst: target.join(relative),
+                    shown_src: source.join(relative),
+                    shown_dst: shown_target.join(relative),
+                }),
+                Err(_) => outside = Some(entry.path.clone()),
+            }
+        })
+        .await?;
+    if let Some(path) = outside {
+        return Err(Error::other(format!(
+            "{} is not under the source, nothing copied",
+            path.display()
+        )));
+    }
+    Ok(Plan {
+        from,
+        files,
+        exit_code,
+    })
+}
+
+/// Copy one file, returning its size.
+///
+/// The content is streamed by parts of [BUFFER_SIZE], the last one being the only shorter one.
+async fn copy(from: &DAL, src: &Path, to: &DAL, dst: &Path) -> io::Result<u64> {
+    let meta = from.from_path(src).await?;
+    let mut reader = from.reader(src, None).await?;
+    let mut buf = vec![0; BUFFER_SIZE];
+    let mut filled = fill(&mut reader, &mut buf).await?;
+    if filled < BUFFER_SIZE {
+        buf.truncate(filled);
+        to.write(dst, buf, meta.content_type()).await?;
+        return Ok(filled as u64);
+    }
+
+    let mut writer = to.writer(dst, meta.content_type()).await?;
+    let mut size = 0;
+    while filled > 0 {
+        writer.append(buf[..filled].to_vec()).await?;
+        size += filled as u64;
+        filled = fill(&mut reader, &mut buf).await?;
+    }
+    writer.close().await?;
+    Ok(size)
+}
+
+/// Read from `reader` until `buf` is full or the content ends, returning the number of bytes
+/// read.
+async fn fill<R: AsyncRead + Unpin>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]).await? {
+            0 => break,
+            n => filled += n,
+        }
+    }
+    Ok(filled)
+}
+
+fn open(work_dir: &Path, input: &Path, config: &Config, flags: &Flags) -> io::Result<(Uri, DAL)> {
+    let uri = Uri::parse(input, config)?;
+    let dal = DAL::new(work_dir, &uri, flags.concurrency.0)?;
+    Ok((uri, dal))
+}
+
+fn human(bytes: u64, flags: &Flags) -> String {
+    let size = Size::new(bytes);
+    format!("{} {}", size.value_string(flags), size.unit_string(flags))
+}
+
+fn plural(count: u64, noun: &str) -> String {
+    if count == 1 {
+        format!("{count} {noun}")
+    } else {
+        format!("{count} {noun}s")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{fill, plan, run, BUFFER_SIZE};
+
+    use crate::app::{Cli, Command};
+    use crate::config_file::Config;
+    use crate::dal::Uri;
+    use crate::flags::Flags;
+    use crate::ExitCode;
+
+    use assert_fs::prelude::*;
+    use clap::Parser;
+    use futures::io::{AsyncReadExt, Cursor};
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    async fn cp(argv: &[&str]) -> ExitCode {
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+        match &cli.command {
+            Some(Command::Cp(args)) => run(args, &Config::with_none(), &flags).await,
+            _ => panic!("not a cp command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_sources_and_destination() {
+        let cli = Cli::try_parse_from(["lsd", "cp", "-r", "a", "b", "dest/"]).unwrap();
+        match cli.command {
+            Some(Command::Cp(args)) => {
+                assert_eq!(2, args.sources.len());
+                assert_eq!("dest/", args.destination.to_str().unwrap());
+                assert!(args.recursive);
+            }
+            _ => panic!("not a cp command"),
+        }
+
+        assert!(Cli::try_parse_from(["lsd", "cp", "a"]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_copy_file() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("a.txt").write_str("hello").unwrap();
+        let src = dir.child("a.txt");
+        let dst = dir.child("b.txt");
+
+        let code = cp(&[
+            "lsd",
+            "cp",
+            src.path().to_str().unwrap(),
+            dst.path().to_str().unwrap(),
+        ])
+        .await;
+        assert!(code == ExitCode::OK);
+        assert_eq!("hello", fs::read_to_string(dst.path()).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_fill_across_short_reads() {
+        let mut reader = Cursor::new(b"abc".to_vec()).chain(Cursor::new(b"defg".to_vec()));
+        let mut buf = [0; 5];
+        assert_eq!(5, fill(&mut reader, &mut buf).await.unwrap());
+        assert_eq!(b"abcde", &buf);
+        assert_eq!(2, fill(&mut reader, &mut buf).await.unwrap());
+        assert_eq!(b"fg", &buf[..2]);
+    }
+
+    #[tokio::test]
+    async fn test_copy_large_file() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let content: Vec<u8> = (0..2 * BUFFER_SIZE + 3).map(|i| i as u8).collect();
+        dir.child("large").write_binary(&content).unwrap();
+
+        let code = cp(&[
+            "lsd",
+            "cp",
+            dir.child("large").path().to_str().unwrap(),
+            dir.child("copy").path().to_str().unwrap(),
+        ])
+        .await;
+        assert!(code == ExitCode::OK);
+        assert!(content == fs::read(dir.child("copy").path()).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_copy_into_directory() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("a.txt").write_str("a").unwrap();
+        dir.child("b.txt").write_str("b").unwrap();
+        dir.child("dest").create_dir_all().unwrap();
+
+        let code = cp(&[
+            "lsd",
+            "cp",
+            dir.child("a.txt").path().to_str().unwrap(),
+            dir.child("b.txt").path().to_str().unwrap(),
+            dir.child("dest").path().to_str().unwrap(),
+        ])
+        .await;
+        assert!(code == ExitCode::OK);
+        assert_eq!(
+            "a",
+            fs::read_to_string(dir.child("dest/a.txt").path()).unwrap()
+        );
+        assert_eq!(
+            "b",
+            fs::read_to_string(dir.child("dest/b.txt").path()).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_copy_directory() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("src/one/a.txt").write_str("a").unwrap();
+        dir.child("src/b.txt").write_str("b").unwrap();
+        let src = dir.child("src");
+        let dst = dir.child("copy");
+
+        let argv = [
+            "lsd",
+            "cp",
+            src.path().to_str().unwrap(),
+            dst.path().to_str().unwrap(),
+        ];
+        assert!(cp(&argv).await == ExitCode::MajorIssue);
+        assert!(!dst.path().exists());
+
+        let argv = [
+            "lsd",
+            "cp",
+            "-r",
+            src.path().to_str().unwrap(),
+            dst.path().to_str().unwrap(),
+        ];
+        assert!(cp(&argv).await == ExitCode::OK);
+        assert_eq!(
+            "a",
+            fs::read_to_string(dst.child("one/a.txt").path()).unwrap()
+        );
+        assert_eq!("b", fs::read_to_string(dst.child("b.txt").path()).unwrap());
+    }
+
+    /// The source and destination paths, as shown, of the files `argv` copies from `work_dir`.
+    async fn planned(work_dir: &Path, argv: &[&str]) -> Vec<(PathBuf, PathBuf)> {
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+        let args = match &cli.command {
+            Some(Command::Cp(args)) => args,
+            _ => panic!("not a cp command"),
+        };
+        let config = Config::with_none();
+        let dest = Uri::parse(&args.destination, &config).unwrap();
+        let plan = plan(
+            work_dir,
+            &args.sources[0],
+            &dest,
+            false,
+            args,
+            &config,
+            &flags,
+        )
+        .await
+        .unwrap();
+        let mut files: Vec<_> = plan
+            .files
+            .into_iter()
+            .map(|job| (job.shown_src, job.shown_dst))
+            .collect();
+        files.sort();
+        files
+    }
+
+    #[tokio::test]
+    async fn test_plan_relative_source() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("src/one/a.txt").touch().unwrap();
+        dir.child("src/.b").touch().unwrap();
+        dir.child("work").create_dir_all().unwrap();
+
+        let expected = vec![
+            (PathBuf::from("./src/.b"), PathBuf::from("copy/.b")),
+            (
+                PathBuf::from("./src/one/a.txt"),
+                PathBuf::from("copy/one/a.txt"),
+            ),
+        ];
+        let argv = ["lsd", "cp", "-r", "./src", "copy"];
+        assert_eq!(expected, planned(dir.path(), &argv).await);
+
+        let expected = vec![
+            (PathBuf::from("../src/.b"), PathBuf::from("copy/.b")),
+            (
+                PathBuf::from("../src/one/a.txt"),
+                PathBuf::from("copy/one/a.txt"),
+            ),
+        ];
+        let argv = ["lsd", "cp", "-r", "../src", "copy"];
+        assert_eq!(expected, planned(dir.child("work").path(), &argv).await);
+    }
+
+    #[tokio::test]
+    async fn test_plan_ignores_listing_filters() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("src/small").write_str("a").unwrap();
+        dir.child("src/skipped/large").write_str("12345").unwrap();
+
+        let argv = [
+            "lsd", "-I", "skipped", "--larger", "3", "cp", "-r", "src", "copy",
+        ];
+        let files = planned(dir.path(), &argv).await;
+        assert_eq!(2, files.len());
+    }
+
+    #[tokio::test]
+    async fn test_copy_missing_source() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let code = cp(&[
+            "lsd",
+            "cp",
+            dir.child("missing").path().to_str().unwrap(),
+            dir.child("dest").path().to_str().unwrap(),
+        ])
+        .await;
+        assert!(code == ExitCode::MajorIssue);
+    }
+}
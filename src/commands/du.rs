@@ -0,0 +1,317 @@
+//! This module implements `dl du`, which sums the sizes and the number of files under each
+//! directory, or prefix, of a path.
+
+use crate::app::DuArgs;
+use crate::config_file::Config;
+use crate::dal::{listed_path, Uri, DAL};
+use crate::flags::{Block, Blocks, Display, Flags, SizeFlag};
+use crate::meta::{FileType, Size};
+use crate::{print_error, print_output, ExitCode};
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The sizes and the number of the files under a directory.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct Usage {
+    bytes: u64,
+    files: u64,
+}
+
+/// Print the usage of each input, and of its directories down to the `--depth`.
+///
+/// An input which can not be walked is reported and skipped, it is a major issue.
+pub async fn run(args: &DuArgs, config: &Config, flags: &Flags) -> ExitCode {
+    let work_dir = match std::env::current_dir() {
+        Ok(dir) => dir,
+        Err(err) => {
+            print_error!("current directory: {}.", err);
+            return ExitCode::MajorIssue;
+        }
+    };
+    let flags = &Flags {
+        size: args
+            .size
+            .as_deref()
+            .map_or(flags.size, SizeFlag::from_arg_str),
+        ..flags.clone()
+    };
+    let depth = if args.summarize {
+        0
+    } else {
+        args.depth.unwrap_or(usize::MAX)
+    };
+
+    let mut exit_code = ExitCode::OK;
+    for input in &args.inputs {
+        match usages(&work_dir, input, depth, config, flags).await {
+            Ok((usages, code)) => {
+                print_output!("{}", render(input, usages, args.sort == "size", flags));
+                exit_code.set_if_greater(code);
+            }
+            Err(err) => {
+                print_error!("{}: {}.", input.display(), err);
+                exit_code.set_if_greater(ExitCode::MajorIssue);
+            }
+        }
+    }
+    exit_code
+}
+
+/// The usage of `input` and of its directories down to `depth`, by their paths relative to
+/// `input`.
+///
+/// Only the sizes of the files are summed, directories have no size of their own on object
+/// stores. The usages of the deeper directories are only kept summed in their parents, so
+/// that the memory does not grow with the number of listed entries. The directories which can
+/// not be listed are reported as a minor issue and left out of the sums.
+async fn usages(
+    work_dir: &Path,
+    input: &Path,
+    depth: usize,
+    config: &Config,
+    flags: &Flags,
+) -> io::Result<(BTreeMap<PathBuf, Usage>, ExitCode)> {
+    let uri = Uri::parse(input, config)?;
+    let dal = DAL::new(work_dir, &uri, flags.concurrency.0)?;
+    let meta = dal.from_path(&uri.path).await?;
+
+    let mut usages = BTreeMap::from([(PathBuf::new(), Usage::default())]);
+    if !matches!(meta.file_type(), FileType::Directory { .. }) {
+        let bytes = meta.size().map_or(0, |s| s.get_bytes());
+        usages.insert(PathBuf::new(), Usage { bytes, files: 1 });
+        return Ok((usages, ExitCode::OK));
+    }
+
+    // Every file uses space, whatever the display flags and the filters of the listings, and
+    // the sizes are always needed.
+    let flags = Flags {
+        concurrency: flags.concurrency,
+        display: Display::AlmostAll,
+        blocks: Blocks(vec![Block::Size, Block::Name]),
+        ..Flags::default()
+    };
+    let base = listed_path(&uri.path);
+    let mut outside = None;
+    let exit_code = dal
+        .walk(&meta, usize::MAX, &flags, |_, entry| {
+            let relative = match entry.path.strip_prefix(&base) {
+                Ok(relative) => relative,
+                Err(_) => {
+                    outside = Some(entry.path.clone());
+                    return;
+                }
+            };
+            if matches!(entry.file_type(), FileType::Directory { .. }) {
+                if relative.components().count() <= depth {
+                    // Without the trailing `/` of the listed directories.
+                    usages.entry(relative.components().collect()).or_default();
+                }
+                return;
+            }
+
+            let bytes = entry.size().map_or(0, |s| s.get_bytes());
+            for dir in relative.ancestors().skip(1) {
+                if dir.components().count() <= depth {
+                    let usage = usages.entry(dir.to_path_buf()).or_default();
+                    usage.bytes += bytes;
+                    usage.files += 1;
+                }
+            }
+        })
+        .await?;
+    if let Some(path) = outside {
+        return Err(io::Error::other(format!(
+            "{} is not under the input",
+            path.display()
+        )));
+    }
+    Ok((usages, exit_code))
+}
+
+/// One line per directory, with its size, its number of files and its path, ordered by path
+/// or from the largest to the smallest.
+fn render(input: &Path, usages: BTreeMap<PathBuf, Usage>, by_size: bool, flags: &Flags) -> String {
+    let mut usages: Vec<_> = usages.into_iter().collect();
+    if by_size {
+        usages.sort_by_key(|(_, usage)| std::cmp::Reverse(usage.bytes));
+    }
+
+    let rows: Vec<_> = usages
+        .into_iter()
+        .map(|(relative, usage)| {
+            let size = Size::new(usage.bytes);
+            let size = format!("{} {}", size.value_string(flags), size.unit_string(flags));
+            let path = if relative.as_os_str().is_empty() {
+                input.to_path_buf()
+            } else {
+                input.join(relative)
+            };
+            (
+                size.trim_end().to_string(),
+                usage.files.to_string(),
+                path.to_string_lossy().to_string(),
+            )
+        })
+        .collect();
+
+    let size_width = rows
+        .iter()
+        .map(|(size, _, _)| size.len())
+        .max()
+        .unwrap_or(0);
+    let files_width = rows
+        .iter()
+        .map(|(_, files, _)| files.len())
+        .max()
+        .unwrap_or(0);
+    let mut output = String::new();
+    for (size, files, path) in rows {
+        output += &format!("{size:>size_width$}  {files:>files_width$}  {path}\n");
+    }
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use super::{render, usages, Usage};
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::{Flags, SizeFlag};
+
+    use assert_fs::prelude::*;
+    use clap::Parser;
+    use std::collections::BTreeMap;
+    use std::path::{Path, PathBuf};
+
+    #[tokio::test]
+    async fn test_usages() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("a/b/one").write_str("0123456789").unwrap();
+        dir.child("a/two").write_str("01234").unwrap();
+        dir.child("a/.hidden").write_str("01").unwrap();
+        dir.child("three").write_str("0").unwrap();
+        dir.child("empty").create_dir_all().unwrap();
+
+        let flags = Flags::default();
+        let all = usages(
+            Path::new("/"),
+            dir.path(),
+            usize::MAX,
+            &Config::with_none(),
+            &flags,
+        )
+        .await
+        .unwrap()
+        .0;
+        let usage = |path: &str| all[Path::new(path)];
+        assert_eq!(
+            Usage {
+                bytes: 18,
+                files: 4
+            },
+            usage("")
+        );
+        assert_eq!(
+            Usage {
+                bytes: 17,
+                files: 3
+            },
+            usage("a")
+        );
+        assert_eq!(
+            Usage {
+                bytes: 10,
+                files: 1
+            },
+            usage("a/b")
+        );
+        assert_eq!(Usage::default(), usage("empty"));
+
+        let one = usages(Path::new("/"), dir.path(), 1, &Config::with_none(), &flags)
+            .await
+            .unwrap()
+            .0;
+        assert_eq!(3, one.len());
+        assert_eq!(
+            Usage {
+                bytes: 17,
+                files: 3
+            },
+            one[Path::new("a")]
+        );
+
+        let summary = usages(Path::new("/"), dir.path(), 0, &Config::with_none(), &flags)
+            .await
+            .unwrap()
+            .0;
+        assert_eq!(1, summary.len());
+    }
+
+    #[tokio::test]
+    async fn test_usages_ignore_listing_filters() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("a/small").write_str("0").unwrap();
+        dir.child("a/skipped/large")
+            .write_str("0123456789")
+            .unwrap();
+        dir.child("work").create_dir_all().unwrap();
+
+        let argv = ["lsd", "-I", "skipped", "--larger", "5"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+        let all = usages(
+            dir.child("work").path(),
+            Path::new("../a"),
+            usize::MAX,
+            &Config::with_none(),
+            &flags,
+        )
+        .await
+        .unwrap()
+        .0;
+        assert_eq!(
+            Usage {
+                bytes: 11,
+                files: 2
+            },
+            all[Path::new("")]
+        );
+    }
+
+    #[test]
+    fn test_render_by_size() {
+        let flags = Flags {
+            size: SizeFlag::Bytes,
+            ..Flags::default()
+        };
+        let usages = BTreeMap::from([
+            (
+                PathBuf::new(),
+                Usage {
+                    bytes: 30,
+                    files: 3,
+                },
+            ),
+            (PathBuf::from("a"), Usage { bytes: 5, files: 1 }),
+            (
+                PathBuf::from("b"),
+                Usage {
+                    bytes: 25,
+                    files: 2,
+                },
+            ),
+        ]);
+
+        assert_eq!(
+            "30  3  logs\n 5  1  logs/a\n25  2  logs/b\n",
+            render(Path::new("logs"), usages.clone(), false, &flags)
+        );
+        assert_eq!(
+            "30  3  logs\n25  2  logs/b\n 5  1  logs/a\n",
+            render(Path::new("logs"), usages, true, &flags)
+        );
+    }
+}
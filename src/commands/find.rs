@@ -0,0 +1,414 @@
+//! This module implements `dl find`, which prints the files under directories and prefixes
+//! matching predicates on their name, size, modification date and type.
+
+use crate::app::{Cli, Comparison, FindArgs, SizePredicate};
+use crate::config_file::Config;
+use crate::core::Core;
+use crate::dal::{listed_path, Meta, Uri, DAL};
+use crate::flags::filters::parse_time;
+use crate::flags::{Block, Blocks, Concurrency, Display, Flags};
+use crate::meta::{Date, FileType};
+use crate::{print_error, print_output, ExitCode};
+
+use chrono::{DateTime, Local};
+use clap::CommandFactory;
+use globset::{GlobBuilder, GlobMatcher};
+
+use std::ffi::OsString;
+use std::io::{self, Error, ErrorKind};
+use std::path::Path;
+
+/// The predicates which are also accepted with a single dash, like in `find`.
+const SINGLE_DASH_PREDICATES: [&str; 8] = [
+    "-name",
+    "-iname",
+    "-size",
+    "-newer",
+    "-mtime",
+    "-type",
+    "-maxdepth",
+    "-print0",
+];
+
+/// Rewrite the predicates of `dl find` written like in `find`, e.g. `-name`, into the long
+/// arguments they are, e.g. `--name`. The arguments of the other commands, e.g. of `dl ls find`
+/// listing a directory named `find`, are kept as they are.
+pub fn long_predicates(args: impl IntoIterator<Item = OsString>) -> Vec<OsString> {
+    let mut args: Vec<OsString> = args.into_iter().collect();
+    if let Some(find) = command_position(&args).filter(|&position| args[position] == "find") {
+        for arg in &mut args[find + 1..] {
+            if SINGLE_DASH_PREDICATES.iter().any(|p| arg == p) {
+                let mut long = OsString::from("-");
+                long.push(&arg);
+                *arg = long;
+            }
+        }
+    }
+    args
+}
+
+/// The position of the command in `args`: the first argument after the binary which is neither
+/// an option nor the value of one.
+fn command_position(args: &[OsString]) -> Option<usize> {
+    let cli = Cli::command();
+    let takes_value = |option: &str| {
+        let arg = match option.strip_prefix("--") {
+            Some(long) => cli.get_arguments().find(|arg| arg.get_long() == Some(long)),
+            None => {
+                let mut shorts = option.chars().skip(1);
+                match (shorts.next(), shorts.next()) {
+                    (Some(short), None) => cli
+                        .get_arguments()
+                        .find(|arg| arg.get_short() == Some(short)),
+                    _ => None,
+                }
+            }
+        };
+        arg.is_some_and(|arg| arg.get_action().takes_values())
+    };
+
+    let mut position = 1;
+    while let Some(arg) = args.get(position) {
+        let arg = arg.to_string_lossy();
+        if arg == "--" {
+            return None;
+        }
+        if !arg.starts_with('-') || arg == "-" {
+            return Some(position);
+        }
+        position += if !arg.contains('=') && takes_value(&arg) {
+            2
+        } else {
+            1
+        };
+    }
+    None
+}
+
+impl Comparison {
+    fn matches(&self, value: u64) -> bool {
+        value.cmp(&self.value) == self.ordering
+    }
+}
+
+impl SizePredicate {
+    /// Whether `bytes`, counted in units rounded up like `find` does, match the comparison.
+    fn matches(&self, bytes: u64) -> bool {
+        self.comparison.matches(bytes.div_ceil(self.unit))
+    }
+}
+
+/// The predicates an entry has to match to be printed.
+struct Predicates {
+    name: Option<GlobMatcher>,
+    size: Option<SizePredicate>,
+    newer: Option<DateTime<Local>>,
+    mtime: Option<Comparison>,
+    file_type: Option<String>,
+}
+
+impl Predicates {
+    fn new(args: &FindArgs) -> io::Result<Self> {
+        let name = match (&args.name, &args.iname) {
+            (Some(pattern), _) => Some(glob(pattern, false)?),
+            (_, Some(pattern)) => Some(glob(pattern, true)?),
+            _ => None,
+        };
+        let newer = args
+            .newer
+            .as_deref()
+            .map(parse_time)
+            .transpose()
+            .map_err(|err| Error::new(ErrorKind::InvalidInput, err))?;
+        Ok(Self {
+            name,
+            size: args.size,
+            newer,
+            mtime: args.mtime,
+            file_type: args.file_type.clone(),
+        })
+    }
+
+    fn matches(&self, meta: &Meta) -> bool {
+        if let Some(name) = &self.name {
+            if !name.is_match(meta.name().file_name()) {
+                return false;
+            }
+        }
+        if let Some(file_type) = &self.file_type {
+            let matched = matches!(
+                (file_type.as_str(), meta.file_type()),
+                ("d", FileType::Directory { .. })
+                    | ("l", FileType::SymLink { .. })
+                    | ("f", FileType::File { .. })
+            );
+            if !matched {
+                return false;
+            }
+        }
+        if let Some(size) = &self.size {
+            match meta.size() {
+                Some(s) if size.matches(s.get_bytes()) => {}
+                _ => return false,
+            }
+        }
+        if self.newer.is_some() || self.mtime.is_some() {
+            let modified = match meta.modified_date() {
+                Date::Date(modified) => modified,
+                Date::Invalid => return false,
+            };
+            if matches!(self.newer, Some(newer) if modified <= newer) {
+                return false;
+            }
+            if let Some(mtime) = &self.mtime {
+                // The age is counted in whole days, like `find` does.
+                let days = (Local::now() - modified).num_days().max(0) as u64;
+                if !mtime.matches(days) {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+}
+
+fn glob(pattern: &str, case_insensitive: bool) -> io::Result<GlobMatcher> {
+    GlobBuilder::new(pattern)
+        .case_insensitive(case_insensitive)
+        .build()
+        .map(|glob| glob.compile_matcher())
+        .map_err(|err| Error::new(ErrorKind::InvalidInput, err.to_string()))
+}
+
+/// Print the inputs, and everything under them, matching all the predicates.
+///
+/// An input which can not be walked is reported and skipped, it is a major issue.
+pub async fn run(args: &FindArgs, config: &Config, flags: &Flags) -> ExitCode {
+    let predicates = match Predicates::new(args) {
+        Ok(predicates) => predicates,
+        Err(err) => {
+            print_error!("{}.", err);
+            return ExitCode::MajorIssue;
+        }
+    };
+    let work_dir = match std::env::current_dir() {
+        Ok(dir) => dir,
+        Err(err) => {
+            print_error!("current directory: {}.", err);
+            return ExitCode::MajorIssue;
+        }
+    };
+
+    let core = Core::new(flags.clone());
+    let print = |meta: &Meta, shown: &Path| {
+        if args.print0 {
+            print_output!("{}\0", shown.display());
+        } else {
+            print_output!("{}\n", core.render_path(meta, shown));
+        }
+    };
+
+    let depth = args.maxdepth.unwrap_or(usize::MAX);
+    let mut exit_code = ExitCode::OK;
+    for input in &args.inputs {
+        let res = find(
+            &work_dir,
+            input,
+            depth,
+            config,
+            flags.concurrency,
+            |meta, shown| {
+                if predicates.matches(meta) {
+                    print(meta, shown);
+                }
+            },
+        )
+        .await;
+        match res {
+            Ok(code) => exit_code.set_if_greater(code),
+            Err(err) => {
+                print_error!("{}: {}.", input.display(), err);
+                exit_code.set_if_greater(ExitCode::MajorIssue);
+            }
+        }
+    }
+    exit_code
+}
+
+/// Visit `input` and everything under it, down to `depth` levels, with the path each entry
+/// is shown as. The directories under `input` which can not be listed are reported as a minor
+/// issue.
+async fn find<F>(
+    work_dir: &Path,
+    input: &Path,
+    depth: usize,
+    config: &Config,
+    concurrency: Concurrency,
+    mut visit: F,
+) -> io::Result<ExitCode>
+where
+    F: FnMut(&Meta, &Path),
+{
+    let uri = Uri::parse(input, config)?;
+    let dal = DAL::new(work_dir, &uri, concurrency.0)?;
+    let meta = dal.from_path(&uri.path).await?;
+    visit(&meta, input);
+    if !matches!(meta.file_type(), FileType::Directory { .. }) {
+        return Ok(ExitCode::OK);
+    }
+
+    // Everything is visited, whatever the display flags and the filters of the listings, and
+    // the sizes and dates are always fetched for the predicates.
+    let flags = Flags {
+        concurrency,
+        display: Display::AlmostAll,
+        blocks: Blocks(vec![Block::Size, Block::Date, Block::Name]),
+        ..Flags::default()
+    };
+    let base = listed_path(&uri.path);
+    let mut outside = None;
+    let exit_code = dal
+        .walk(&meta, depth, &flags, |_, entry| {
+            match entry.path.strip_prefix(&base) {
+                Ok(relative) => visit(&entry, &input.join(relative)),
+                Err(_) => outside = Some(entry.path.clone()),
+            }
+        })
+        .await?;
+    if let Some(path) = outside {
+        return Err(Error::other(format!(
+            "{} is not under the input",
+            path.display()
+        )));
+    }
+    Ok(exit_code)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{find, long_predicates, Predicates};
+
+    use crate::app::{Cli, Command};
+    use crate::config_file::Config;
+    use crate::flags::Flags;
+
+    use assert_fs::prelude::*;
+    use clap::Parser;
+    use std::ffi::OsString;
+    use std::path::Path;
+
+    /// The names of the files under `dir` matching the predicates of `argv`.
+    async fn found(dir: &Path, argv: &[&str]) -> Vec<String> {
+        let args = long_predicates(argv.iter().map(OsString::from));
+        let cli = Cli::try_parse_from(args).unwrap();
+        let args = match &cli.command {
+            Some(Command::Find(args)) => args,
+            _ => panic!("not a find command"),
+        };
+        let predicates = Predicates::new(args).unwrap();
+
+        let mut names = vec![];
+        find(
+            Path::new("/"),
+            dir,
+            usize::MAX,
+            &Config::with_none(),
+            Flags::default().concurrency,
+            |meta, _| {
+                if predicates.matches(meta) {
+                    names.push(meta.name().file_name().to_string());
+                }
+            },
+        )
+        .await
+        .unwrap();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_long_predicates() {
+        let args = long_predicates(
+            ["lsd", "-name", "find", "-name", "*.rs", "-print0"].map(OsString::from),
+        );
+        assert_eq!(
+            vec!["lsd", "-name", "find", "--name", "*.rs", "--print0"],
+            args
+        );
+
+        let args =
+            long_predicates(["lsd", "--color", "never", "find", "-type", "f"].map(OsString::from));
+        assert_eq!(vec!["lsd", "--color", "never", "find", "--type", "f"], args);
+    }
+
+    #[test]
+    fn test_long_predicates_not_find() {
+        for argv in [
+            vec!["lsd", "ls", "find", "-name"],
+            vec!["lsd", "./find", "-type"],
+            vec!["lsd", "find-results", "-size"],
+            vec!["lsd", "--ignore-glob", "find", "dir", "-newer"],
+            vec!["lsd", "--", "find", "-name"],
+        ] {
+            let args = long_predicates(argv.iter().map(OsString::from));
+            assert_eq!(argv, args);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_find_name_and_type() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("src/main.rs").touch().unwrap();
+        dir.child("src/Lib.RS").touch().unwrap();
+        dir.child("README.md").touch().unwrap();
+
+        assert_eq!(
+            vec!["main.rs"],
+            found(dir.path(), &["lsd", "find", "-name", "*.rs"]).await
+        );
+        assert_eq!(
+            vec!["Lib.RS", "main.rs"],
+            found(dir.path(), &["lsd", "find", "-iname", "*.rs"]).await
+        );
+        assert_eq!(
+            vec!["src"],
+            found(dir.path(), &["lsd", "find", "-type", "d", "-name", "s*"]).await
+        );
+    }
+
+    #[tokio::test]
+    async fn test_find_size() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("small").write_str("0123").unwrap();
+        dir.child("large").write_binary(&[0; 2048]).unwrap();
+
+        let argv = ["lsd", "find", "-type", "f", "-size", "+1k"];
+        assert_eq!(vec!["large"], found(dir.path(), &argv).await);
+        let argv = ["lsd", "find", "-type", "f", "-size", "-5"];
+        assert_eq!(vec!["small"], found(dir.path(), &argv).await);
+        let argv = ["lsd", "find", "-type", "f", "-size", "1k"];
+        assert_eq!(vec!["small"], found(dir.path(), &argv).await);
+    }
+
+    #[tokio::test]
+    async fn test_find_dates() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("new").touch().unwrap();
+
+        let argv = ["lsd", "find", "-type", "f", "-mtime", "-1"];
+        assert_eq!(vec!["new"], found(dir.path(), &argv).await);
+        let argv = ["lsd", "find", "-type", "f", "-mtime", "+7"];
+        assert!(found(dir.path(), &argv).await.is_empty());
+        let argv = ["lsd", "find", "-type", "f", "-newer", "2000-01-01"];
+        assert_eq!(vec!["new"], found(dir.path(), &argv).await);
+        let argv = [
+            "lsd",
+            "find",
+            "-type",
+            "f",
+            "-newer",
+            "2999-01-01T00:00:00Z",
+        ];
+        assert!(found(dir.path(), &argv).await.is_empty());
+    }
+}
@@ -0,0 +1,452 @@
+//! This module implements `dl rm`, which removes files, or whole directories and prefixes.
+
+use crate::app::RmArgs;
+use crate::config_file::Config;
+use crate::dal::{listed_path, Uri, DAL};
+use crate::flags::{Concurrency, Display, Flags, IgnoreGlobs};
+use crate::meta::FileType;
+use crate::{print_error, print_output, ExitCode};
+
+use std::io::{self, BufRead, Error, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+/// The objects to remove for one input.
+struct Plan {
+    dal: DAL,
+    /// The files, with their paths in the service and as shown to the user.
+    files: Vec<(PathBuf, PathBuf)>,
+    /// The directories, deepest first, so that each of them is empty once removed.
+    dirs: Vec<(PathBuf, PathBuf)>,
+}
+
+impl Plan {
+    fn len(&self) -> usize {
+        self.files.len() + self.dirs.len()
+    }
+
+    /// The objects to remove, as shown to the user.
+    fn shown(&self) -> impl Iterator<Item = &PathBuf> {
+        self.files
+            .iter()
+            .chain(self.dirs.iter())
+            .map(|(_, shown)| shown)
+    }
+}
+
+/// The patterns selecting the files to remove under a directory.
+struct Filter {
+    include: Option<IgnoreGlobs>,
+    exclude: IgnoreGlobs,
+}
+
+impl Filter {
+    fn is_empty(&self) -> bool {
+        self.include.is_none() && self.exclude.0.is_empty()
+    }
+
+    fn matches(&self, relative: &Path) -> bool {
+        let included = match &self.include {
+            Some(include) => include.0.is_match(relative),
+            None => true,
+        };
+        included && !self.exclude.0.is_match(relative)
+    }
+}
+
+/// Remove the inputs, after printing them under `--dry-run` or asking for a confirmation when
+/// there are more objects to remove than `--confirm-over`.
+///
+/// An input which can not be listed is a major issue, an object which fails to be removed is a
+/// minor one, the other objects are still removed. When the removal is not confirmed, nothing
+/// is removed.
+pub async fn run(args: &RmArgs, config: &Config, flags: &Flags) -> ExitCode {
+    let filter = Filter {
+        include: if args.include.is_empty() {
+            None
+        } else {
+            Some(IgnoreGlobs::from_patterns(&args.include).unwrap_or_else(|err| err.exit()))
+        },
+        exclude: IgnoreGlobs::from_patterns(&args.exclude).unwrap_or_else(|err| err.exit()),
+    };
+    let work_dir = match std::env::current_dir() {
+        Ok(dir) => dir,
+        Err(err) => {
+            print_error!("current directory: {}.", err);
+            return ExitCode::MajorIssue;
+        }
+    };
+
+    let mut exit_code = ExitCode::OK;
+    let mut plans = vec![];
+    for input in &args.inputs {
+        match plan(&work_dir, input, args, &filter, config, flags.concurrency).await {
+            Ok(plan) => plans.push(plan),
+            Err(err) => {
+                print_error!("{}: {}.", input.display(), err);
+                exit_code.set_if_greater(ExitCode::MajorIssue);
+            }
+        }
+    }
+
+    let total: usize = plans.iter().map(Plan::len).sum();
+    if args.dry_run {
+        for shown in plans.iter().flat_map(Plan::shown) {
+            print_output!("would remove {}\n", shown.display());
+        }
+        print_output!("{} would be removed\n", objects(total));
+        return exit_code;
+    }
+
+    if total > args.confirm_over && !args.yes && !confirm(&plans, total) {
+        print_error!("nothing removed.");
+        exit_code.set_if_greater(ExitCode::MajorIssue);
+        return exit_code;
+    }
+
+    let mut removed = 0;
+    let mut failed = 0;
+    for plan in &plans {
+        let paths: Vec<PathBuf> = plan.files.iter().map(|(path, _)| path.clone()).collect();
+        let results = match plan.dal.remove(&paths).await {
+            Ok(()) => plan.files.iter().map(|_| Ok(())).collect(),
+            // The batch may have stopped halfway, the files are removed again one by one to
+            // tell which of them failed, the ones already gone being removed successfully.
+            Err(_) => {
+                let mut results = vec![];
+                for path in &paths {
+                    results.push(plan.dal.remove(std::slice::from_ref(path)).await);
+                }
+                results
+            }
+        };
+        for ((_, shown), result) in plan.files.iter().zip(results) {
+            match result {
+                Ok(()) => {
+                    print_output!("removed {}\n", shown.display());
+                    removed += 1;
+                }
+                Err(err) => {
+                    print_error!("{}: {}.", shown.display(), err);
+                    failed += 1;
+                    exit_code.set_if_greater(ExitCode::MinorIssue);
+                }
+            }
+        }
+
+        // A directory keeping a file which failed to be removed fails to be removed too.
+        for (path, shown) in &plan.dirs {
+            match plan.dal.remove_dir(path).await {
+                Ok(()) => {
+                    print_output!("removed {}\n", shown.display());
+                    removed += 1;
+                }
+                Err(err) => {
+                    print_error!("{}: {}.", shown.display(), err);
+                    failed += 1;
+                    exit_code.set_if_greater(ExitCode::MinorIssue);
+                }
+            }
+        }
+    }
+    print_output!("{} removed, {} failed\n", objects(removed), failed);
+    exit_code
+}
+
+/// List the objects to remove for `input`.
+///
+/// When `--include` or `--exclude` are given, only the matching files are removed, and the
+/// directories are kept.
+async fn plan(
+    work_dir: &Path,
+    input: &Path,
+    args: &RmArgs,
+    filter: &Filter,
+    config: &Config,
+    concurrency: Concurrency,
+) -> io::Result<Plan> {
+    // Like `rm`, whose `.` and `..` would remove the working directory or its parent.
+    let name = input.to_string_lossy();
+    if matches!(
+        name.trim_end_matches('/').rsplit('/').next(),
+        Some("." | "..")
+    ) {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "refusing to remove a . or .. directory",
+        ));
+    }
+
+    let uri = Uri::parse(input, config)?;
+    let dal = DAL::new(work_dir, &uri, concurrency.0)?;
+    let meta = dal.from_path(&uri.path).await?;
+
+    // A link to a directory is removed itself, never the content of its target.
+    if !matches!(meta.file_type(), FileType::Directory { .. }) {
+        let name = meta.name().file_name().to_string();
+        let files = if filter.matches(Path::new(&name)) {
+            vec![(uri.path, input.to_path_buf())]
+        } else {
+            vec![]
+        };
+        return Ok(Plan {
+            dal,
+            files,
+            dirs: vec![],
+        });
+    }
+    if !args.recursive {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "is a directory, use -r to remove it",
+        ));
+    }
+
+    // Everything is listed, whatever the display flags, so that the directories can be emptied.
+    let flags = Flags {
+        concurrency,
+        display: Display::AlmostAll,
+        ..Flags::default()
+    };
+    let base = listed_path(&uri.path);
+    let mut files = vec![];
+    let mut dirs = vec![];
+    let mut outside = None;
+    let exit_code = dal
+        .walk(&meta, usize::MAX, &flags, |_, entry| {
+            let relative = match entry.path.strip_prefix(&base) {
+                Ok(relative) => relative,
+                Err(_) => {
+                    outside = Some(entry.path.clone());
+                    return;
+                }
+            };
+            let shown = input.join(relative);
+            if matches!(entry.file_type(), FileType::Directory { .. }) {
+                dirs.push((entry.path.clone(), shown));
+            } else if filter.matches(relative) {
+                files.push((entry.path.clone(), shown));
+            }
+        })
+        .await?;
+    if let Some(path) = outside {
+        return Err(Error::other(format!(
+            "{} is not under the input, nothing removed",
+            path.display()
+        )));
+    }
+    // A directory which could not be listed could not be emptied either.
+    if exit_code != ExitCode::OK {
+        return Err(Error::other(
+            "some directories could not be listed, nothing removed",
+        ));
+    }
+
+    if !filter.is_empty() {
+        dirs.clear();
+    } else {
+        // The walk goes level by level, the deepest directories are the last ones.
+        dirs.reverse();
+        if !base.as_os_str().is_empty() {
+            dirs.push((uri.path.clone(), input.to_path_buf()));
+        }
+    }
+    Ok(Plan { dal, files, dirs })
+}
+
+/// List the `count` objects of the plans on the terminal, and ask whether to remove them.
+fn confirm(plans: &[Plan], count: usize) -> bool {
+    #[cfg(not(target_os = "windows"))]
+    let tty_available = unsafe { libc::isatty(libc::STDIN_FILENO) == 1 };
+    #[cfg(target_os = "windows")]
+    let tty_available = true;
+
+    if !tty_available {
+        print_error!(
+            "{} would be removed, pass --yes to confirm.",
+            objects(count)
+        );
+        return false;
+    }
+
+    for shown in plans.iter().flat_map(Plan::shown) {
+        eprintln!("{}", shown.display());
+    }
+    eprint!("dl: remove {}? [y/N] ", objects(count));
+    let _ = io::stderr().flush();
+    let mut answer = String::new();
+    if io::stdin().lock().read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim(), "y" | "Y" | "yes" | "Yes")
+}
+
+fn objects(count: usize) -> String {
+    if count == 1 {
+        "1 object".to_string()
+    } else {
+        format!("{count} objects")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{plan, run, Filter};
+
+    use crate::app::{Cli, Command};
+    use crate::config_file::Config;
+    use crate::flags::{Flags, IgnoreGlobs};
+    use crate::ExitCode;
+
+    use assert_fs::prelude::*;
+    use clap::Parser;
+    use std::path::{Path, PathBuf};
+
+    async fn rm(argv: &[&str]) -> ExitCode {
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+        match &cli.command {
+            Some(Command::Rm(args)) => run(args, &Config::with_none(), &flags).await,
+            _ => panic!("not a rm command"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_remove_file() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let file = dir.child("a.txt");
+        file.touch().unwrap();
+
+        let code = rm(&["lsd", "rm", file.path().to_str().unwrap()]).await;
+        assert!(code == ExitCode::OK);
+        assert!(!file.path().exists());
+    }
+
+    #[tokio::test]
+    async fn test_remove_directory_requires_recursive() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("sub/a.txt").touch().unwrap();
+        let sub = dir.child("sub");
+
+        let code = rm(&["lsd", "rm", sub.path().to_str().unwrap()]).await;
+        assert!(code == ExitCode::MajorIssue);
+        assert!(sub.path().exists());
+
+        let code = rm(&["lsd", "rm", "-r", sub.path().to_str().unwrap()]).await;
+        assert!(code == ExitCode::OK);
+        assert!(!sub.path().exists());
+    }
+
+    /// The objects, as shown, `rm -r` would remove for `input` from `work_dir`.
+    async fn planned(work_dir: &Path, input: &str) -> std::io::Result<Vec<PathBuf>> {
+        let cli = Cli::try_parse_from(["lsd", "rm", "-r", input]).unwrap();
+        let args = match &cli.command {
+            Some(Command::Rm(args)) => args,
+            _ => panic!("not a rm command"),
+        };
+        let filter = Filter {
+            include: None,
+            exclude: IgnoreGlobs::default(),
+        };
+        let flags = Flags::default();
+        let plan = plan(
+            work_dir,
+            Path::new(input),
+            args,
+            &filter,
+            &Config::with_none(),
+            flags.concurrency,
+        )
+        .await?;
+        Ok(plan.shown().cloned().collect())
+    }
+
+    #[tokio::test]
+    async fn test_plan_relative_input() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("sub/a").touch().unwrap();
+        dir.child("sub/deep/b").touch().unwrap();
+        dir.child("work").create_dir_all().unwrap();
+
+        let mut shown = planned(dir.path(), "./sub").await.unwrap();
+        shown.sort();
+        let expected: Vec<PathBuf> = ["./sub", "./sub/a", "./sub/deep", "./sub/deep/b"]
+            .iter()
+            .map(PathBuf::from)
+            .collect();
+        assert_eq!(expected, shown);
+
+        let shown = planned(dir.child("work").path(), "../sub").await.unwrap();
+        assert_eq!(4, shown.len());
+    }
+
+    #[tokio::test]
+    async fn test_refuse_dot_and_dot_dot() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("sub/a").touch().unwrap();
+        let work = dir.child("sub");
+
+        for input in [".", "./", "..", "../", "../sub/.", "sub/.."] {
+            assert!(planned(work.path(), input).await.is_err(), "{input}");
+        }
+        assert!(dir.child("sub/a").path().exists());
+    }
+
+    #[tokio::test]
+    async fn test_dry_run() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("sub/a.txt").touch().unwrap();
+        let sub = dir.child("sub");
+
+        let code = rm(&["lsd", "rm", "-r", "-n", sub.path().to_str().unwrap()]).await;
+        assert!(code == ExitCode::OK);
+        assert!(dir.child("sub/a.txt").path().exists());
+    }
+
+    #[tokio::test]
+    async fn test_include_and_exclude() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("sub/a.log").touch().unwrap();
+        dir.child("sub/keep.log").touch().unwrap();
+        dir.child("sub/b.txt").touch().unwrap();
+        dir.child("sub/deep/c.log").touch().unwrap();
+        let sub = dir.child("sub");
+
+        let code = rm(&[
+            "lsd",
+            "rm",
+            "-r",
+            "--include",
+            "*.log",
+            "--exclude",
+            "keep*",
+            sub.path().to_str().unwrap(),
+        ])
+        .await;
+        assert!(code == ExitCode::OK);
+        assert!(!dir.child("sub/a.log").path().exists());
+        assert!(!dir.child("sub/deep/c.log").path().exists());
+        assert!(dir.child("sub/keep.log").path().exists());
+        assert!(dir.child("sub/b.txt").path().exists());
+        assert!(dir.child("sub/deep").path().exists());
+    }
+
+    #[tokio::test]
+    async fn test_confirm_over_with_yes() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("sub/a").touch().unwrap();
+        dir.child("sub/b").touch().unwrap();
+        let sub = dir.child("sub");
+
+        let argv = [
+            "lsd",
+            "rm",
+            "-r",
+            "--confirm-over",
+            "1",
+            "--yes",
+            sub.path().to_str().unwrap(),
+        ];
+        assert!(rm(&argv).await == ExitCode::OK);
+        assert!(!sub.path().exists());
+    }
+}
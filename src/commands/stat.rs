@@ -0,0 +1,174 @@
+//! This module implements `dl stat`, which prints all the metadata the storage returns for files.
+
+use crate::app::StatArgs;
+use crate::config_file::Config;
+use crate::dal::{Meta, Uri, DAL};
+use crate::flags::Flags;
+use crate::{print_error, print_output, record, ExitCode};
+
+use serde::Serialize;
+
+use std::io;
+use std::path::Path;
+
+/// The metadata of a file, as returned by the service.
+///
+/// OpenDAL 0.30 does not return the version of objects yet, so it is not part of it.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct Stat<'a> {
+    path: String,
+    mode: &'static str,
+    content_length: Option<u64>,
+    content_type: Option<&'a str>,
+    content_md5: Option<&'a str>,
+    etag: Option<&'a str>,
+    content_disposition: Option<&'a str>,
+    last_modified: Option<String>,
+}
+
+impl<'a> Stat<'a> {
+    fn new(input: &Path, meta: &'a Meta) -> Self {
+        Self {
+            path: input.to_string_lossy().to_string(),
+            mode: record::type_name(meta.file_type()),
+            content_length: meta.size().map(|s| s.get_bytes()),
+            content_type: meta.content_type(),
+            content_md5: meta.content_md5(),
+            etag: meta.etag(),
+            content_disposition: meta.content_disposition(),
+            last_modified: record::modified(meta),
+        }
+    }
+
+    /// One `Field: value` line per field, the fields the service did not return are `-`.
+    fn human(&self) -> String {
+        let fields = [
+            ("Path", Some(self.path.clone())),
+            ("Mode", Some(self.mode.to_string())),
+            ("Content-Length", self.content_length.map(|l| l.to_string())),
+            ("Content-Type", self.content_type.map(String::from)),
+            ("Content-MD5", self.content_md5.map(String::from)),
+            ("ETag", self.etag.map(String::from)),
+            (
+                "Content-Disposition",
+                self.content_disposition.map(String::from),
+            ),
+            ("Last-Modified", self.last_modified.clone()),
+        ];
+
+        let width = fields.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+        let mut output = String::new();
+        for (name, value) in fields {
+            output += &format!(
+                "{:>width$}: {}\n",
+                name,
+                value.as_deref().unwrap_or("-"),
+                width = width
+            );
+        }
+        output
+    }
+}
+
+/// Print the metadata of each input, as one block of lines or as one JSON object per line.
+///
+/// An input which can not be found is reported and skipped, it is a major issue.
+pub async fn run(args: &StatArgs, config: &Config, flags: &Flags) -> ExitCode {
+    let work_dir = match std::env::current_dir() {
+        Ok(dir) => dir,
+        Err(err) => {
+            print_error!("current directory: {}.", err);
+            return ExitCode::MajorIssue;
+        }
+    };
+
+    let mut exit_code = ExitCode::OK;
+    for (i, input) in args.inputs.iter().enumerate() {
+        let meta = match stat(&work_dir, input, config, flags).await {
+            Ok(meta) => meta,
+            Err(err) => {
+                print_error!("{}: {}.", input.display(), err);
+                exit_code.set_if_greater(ExitCode::MajorIssue);
+                continue;
+            }
+        };
+
+        let stat = Stat::new(input, &meta);
+        if args.json {
+            let line = serde_json::to_string(&stat).expect("failed to serialise the metadata");
+            print_output!("{}\n", line);
+        } else {
+            if i > 0 {
+                print_output!("\n");
+            }
+            print_output!("{}", stat.human());
+        }
+    }
+    exit_code
+}
+
+async fn stat(work_dir: &Path, input: &Path, config: &Config, flags: &Flags) -> io::Result<Meta> {
+    let uri = Uri::parse(input, config)?;
+    let dal = DAL::new(work_dir, &uri, flags.concurrency.0)?;
+    let mut meta = dal.from_path(&uri.path).await?;
+    if flags.dereference.0 {
+        meta.dereference();
+    }
+    Ok(meta)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{stat, Stat};
+
+    use crate::config_file::Config;
+    use crate::flags::Flags;
+
+    use assert_fs::prelude::*;
+    use std::path::Path;
+
+    #[tokio::test]
+    async fn test_stat_file() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let file = dir.child("a.txt");
+        file.write_str("hello").unwrap();
+
+        let meta = stat(
+            Path::new("/"),
+            file.path(),
+            &Config::with_none(),
+            &Flags::default(),
+        )
+        .await
+        .unwrap();
+        let stat = Stat::new(Path::new("a.txt"), &meta);
+        assert_eq!("file", stat.mode);
+        assert_eq!(Some(5), stat.content_length);
+        assert!(stat.last_modified.is_some());
+
+        let human = stat.human();
+        assert!(human.contains("               Path: a.txt\n"));
+        assert!(human.contains("     Content-Length: 5\n"));
+        assert!(human.contains("       Content-Type: -\n"));
+
+        let value: serde_json::Value =
+            serde_json::from_str(&serde_json::to_string(&stat).unwrap()).unwrap();
+        assert_eq!(5, value["content-length"]);
+        assert!(value["etag"].is_null());
+    }
+
+    #[tokio::test]
+    async fn test_stat_missing() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let missing = dir.child("missing");
+        assert!(stat(
+            Path::new("/"),
+            missing.path(),
+            &Config::with_none(),
+            &Flags::default()
+        )
+        .await
+        .is_err());
+    }
+}
@@ -1,14 +1,18 @@
+use crate::app::Cli;
 use crate::flags::display::Display;
+use crate::flags::format::Format;
 use crate::flags::icons::{IconOption, IconTheme};
 use crate::flags::layout::Layout;
+use crate::flags::permission::PermissionFlag;
 use crate::flags::size::SizeFlag;
-use crate::flags::sorting::{DirGrouping, SortColumn};
+use crate::flags::sorting::DirGrouping;
 use crate::flags::HyperlinkOption;
 use crate::flags::{ColorOption, ThemeOption};
 ///! This module provides methods to handle the program's config files and operations related to
 ///! this.
 use crate::print_error;
 
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use serde::Deserialize;
@@ -29,16 +33,28 @@ pub struct Config {
     pub blocks: Option<Vec<String>>,
     pub color: Option<Color>,
     pub date: Option<String>,
+    pub dereference: Option<bool>,
     pub display: Option<Display>,
+    pub filters: Option<Filters>,
+    pub format: Option<Format>,
+    pub hide_dot_keys: Option<bool>,
     pub icons: Option<Icons>,
     pub ignore_globs: Option<Vec<String>>,
+    pub indicators: Option<bool>,
     pub layout: Option<Layout>,
     pub recursion: Option<Recursion>,
     pub size: Option<SizeFlag>,
+    pub permission: Option<PermissionFlag>,
     pub sorting: Option<Sorting>,
+    pub no_symlink: Option<bool>,
     pub total_size: Option<bool>,
+    pub total_size_limit: Option<TotalSizeLimit>,
+    pub symlink_arrow: Option<String>,
     pub hyperlink: Option<HyperlinkOption>,
     pub header: Option<bool>,
+    pub concurrency: Option<usize>,
+    pub s3: Option<S3>,
+    pub profiles: Option<HashMap<String, Profile>>,
 }
 
 #[derive(Eq, PartialEq, Debug, Deserialize)]
@@ -60,14 +76,90 @@ pub struct Recursion {
     pub depth: Option<usize>,
 }
 
+#[derive(Eq, PartialEq, Debug, Deserialize)]
+pub struct TotalSizeLimit {
+    pub depth: Option<usize>,
+    pub timeout: Option<u64>,
+}
+
+#[derive(Eq, PartialEq, Debug, Deserialize)]
+pub struct Filters {
+    pub larger: Option<String>,
+    pub smaller: Option<String>,
+    pub newer: Option<String>,
+    pub older: Option<String>,
+}
+
 #[derive(Eq, PartialEq, Debug, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct Sorting {
-    pub column: Option<SortColumn>,
+    pub column: Option<String>,
     pub reverse: Option<bool>,
     pub dir_grouping: Option<DirGrouping>,
 }
 
+/// A named storage, addressable as `<name>:/path`.
+///
+/// Besides the service configuration, a profile can override the display of its listings.
+#[derive(Eq, PartialEq, Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    #[serde(rename = "type")]
+    pub service: String,
+    pub bucket: Option<String>,
+    pub root: Option<String>,
+    pub endpoint: Option<String>,
+    pub region: Option<String>,
+    pub path_style: Option<bool>,
+    pub anonymous: Option<bool>,
+    /// The directory of a `sled` or `rocksdb` store.
+    pub datadir: Option<String>,
+    /// The character splitting the keys of key-value stores into directories.
+    pub delimiter: Option<String>,
+    pub credential: Option<Credential>,
+    pub blocks: Option<Vec<String>>,
+    pub date: Option<String>,
+}
+
+/// The settings of the `s3://` inputs which do not use a [Profile].
+#[derive(Eq, PartialEq, Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[serde(deny_unknown_fields)]
+pub struct S3 {
+    pub endpoint: Option<String>,
+    pub region: Option<String>,
+    pub path_style: Option<bool>,
+    pub anonymous: Option<bool>,
+    pub credential: Option<Credential>,
+}
+
+/// Where the credential of a [Profile] comes from.
+#[derive(Eq, PartialEq, Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Credential {
+    /// The keys are written in the config file.
+    #[serde(rename_all = "kebab-case")]
+    Static {
+        access_key_id: String,
+        secret_access_key: String,
+    },
+    /// The keys are read from the named environment variables.
+    #[serde(rename_all = "kebab-case")]
+    Env {
+        access_key_id: String,
+        secret_access_key: String,
+    },
+    /// The keys are read from the named profile of the AWS shared credentials file,
+    /// `~/.aws/credentials` unless `AWS_SHARED_CREDENTIALS_FILE` says otherwise.
+    AwsProfile(String),
+    /// The user name and password of the HTTP basic authentication, for `webdav` and `http`,
+    /// or of the `AUTH` of `redis`.
+    Basic { username: String, password: String },
+    /// The token of the HTTP bearer authentication, for `webdav` and `http`.
+    Bearer(String),
+}
+
 impl Config {
     /// This constructs a Config struct with all None
     pub fn with_none() -> Self {
@@ -76,17 +168,93 @@ impl Config {
             blocks: None,
             color: None,
             date: None,
+            dereference: None,
             display: None,
+            filters: None,
+            format: None,
+            hide_dot_keys: None,
             icons: None,
             ignore_globs: None,
+            indicators: None,
             layout: None,
             recursion: None,
             size: None,
+            permission: None,
             sorting: None,
+            no_symlink: None,
             total_size: None,
+            total_size_limit: None,
+            symlink_arrow: None,
             hyperlink: None,
             header: None,
+            concurrency: None,
+            s3: None,
+            profiles: None,
+        }
+    }
+
+    /// This returns the [Profile] addressed by an input like `<name>:/path`, if any.
+    pub fn profile_of(&self, input: &Path) -> Option<(&str, &Profile)> {
+        let input = input.to_str()?;
+        let (name, _) = input.split_once(':')?;
+        self.profiles
+            .as_ref()?
+            .get_key_value(name)
+            .map(|(name, profile)| (name.as_str(), profile))
+    }
+
+    /// This applies the display overrides of the [Profile] used by the first input.
+    ///
+    /// Command line arguments still take precedence over the overrides.
+    pub fn with_profile_overrides(mut self, inputs: &[PathBuf]) -> Self {
+        let overrides = inputs
+            .first()
+            .and_then(|input| self.profile_of(input))
+            .map(|(_, profile)| (profile.blocks.clone(), profile.date.clone()));
+
+        if let Some((blocks, date)) = overrides {
+            if blocks.is_some() {
+                self.blocks = blocks;
+            }
+            if date.is_some() {
+                self.date = date;
+            }
         }
+        self
+    }
+
+    /// This applies the S3 settings passed on the command line to the `s3` section and to the
+    /// S3 [Profile]s, over the ones of the config file.
+    pub fn with_s3_overrides(mut self, cli: &Cli) -> Self {
+        let overrides = S3 {
+            endpoint: cli.endpoint.clone(),
+            region: cli.region.clone(),
+            path_style: cli.path_style.then_some(true),
+            anonymous: cli.anonymous.then_some(true),
+            credential: cli.aws_profile.clone().map(Credential::AwsProfile),
+        };
+        if overrides == S3::default() {
+            return self;
+        }
+
+        let s3 = self.s3.get_or_insert_with(S3::default);
+        override_with(&mut s3.endpoint, &overrides.endpoint);
+        override_with(&mut s3.region, &overrides.region);
+        override_with(&mut s3.path_style, &overrides.path_style);
+        override_with(&mut s3.anonymous, &overrides.anonymous);
+        override_with(&mut s3.credential, &overrides.credential);
+        let profiles = self
+            .profiles
+            .iter_mut()
+            .flat_map(|profiles| profiles.values_mut());
+        for profile in profiles.filter(|p| p.service.eq_ignore_ascii_case("s3")) {
+            override_with(&mut profile.endpoint, &overrides.endpoint);
+            override_with(&mut profile.region, &overrides.region);
+            override_with(&mut profile.path_style, &overrides.path_style);
+            override_with(&mut profile.anonymous, &overrides.anonymous);
+            override_with(&mut profile.credential, &overrides.credential);
+        }
+        self
     }
 
     /// This constructs a Config struct with a passed file path.
@@ -119,7 +287,7 @@ impl Config {
 
     /// This constructs a Config struct with a passed [Yaml] str.
     /// If error happened, return the [serde_yaml::Error].
-    fn from_yaml(yaml: &str) -> Result<Self, serde_yaml::Error> {
+    pub fn from_yaml(yaml: &str) -> Result<Self, serde_yaml::Error> {
         serde_yaml::from_str::<Self>(yaml)
     }
 
@@ -170,6 +338,13 @@ impl Config {
     }
 }
 
+/// Replace `value` with `with`, when it is set.
+fn override_with<T: Clone>(value: &mut Option<T>, with: &Option<T>) {
+    if with.is_some() {
+        value.clone_from(with);
+    }
+}
+
 impl Default for Config {
     fn default() -> Self {
         if let Some(p) = Self::config_file_path() {
@@ -191,11 +366,16 @@ classic: false
 
 # == Blocks ==
 # This specifies the columns and their order when using the long and the tree
-# layout.
-# Possible values: permission, user, group, context, size, date, name, inode
+# layout. The permission, user, group, inode and links are only on the local
+# filesystem, the long layout leaves them out when no input is on it.
+# Possible values: permission, user, group, size, date, name, inode, links,
+# content-type, etag, content-md5, content-disposition
 blocks:
-  - date
+  - permission
+  - user
+  - group
   - size
+  - date
   - name
 
 # == Color ==
@@ -220,11 +400,40 @@ color:
 # Possible values: date, relative, +<date_format>
 # date: date
 
+# == Dereference ==
+# Whether to dereference symbolic links, for local files only.
+# Possible values: false, true
+dereference: false
+
 # == Display ==
 # What items to display. Do not specify this for the default behavior.
 # Possible values: all, almost-all, directory-only
 # display: all
 
+# == Filters ==
+# Only display the files in these size and modification time ranges, the
+# directories are always displayed. The sizes are like "42", "1.5 MB" or "3G",
+# the times are dates like "2026-01-01" or durations like "3 days ago" or "3d".
+# filters:
+#   larger: 1MB
+#   smaller: 1GB
+#   newer: 7d
+#   older: 2026-01-01
+
+# == Format ==
+# How to print the entries. "text" is the grid or the tree, the others are for
+# scripts: "ndjson" prints one JSON object per entry as soon as it is listed,
+# "csv" and "tsv" have a header row and one column per block, after a "path"
+# column with --recursive or --tree.
+# Possible values: text, json, ndjson, csv, tsv
+format: text
+
+# == Hide Dot Keys ==
+# Whether to hide the keys under a "."-prefixed segment on object stores, like
+# the dotfiles on the local filesystem. "-a" and "-A" still show them.
+# Possible values: false, true
+hide-dot-keys: false
+
 # == Icons ==
 icons:
   # When to use icons.
@@ -243,6 +452,12 @@ icons:
 # ignore-globs:
 #   - .git
 
+# == Indicators ==
+# Whether to display indicator characters for certain file types, like "/" for
+# directories.
+# Possible values: false, true
+indicators: false
+
 # == Layout ==
 # Which layout to use. "oneline" might be a bit confusing here and should be
 # called "one-per-line". It might be changed in the future.
@@ -263,10 +478,18 @@ recursion:
 # Possible values: default, short, bytes
 size: default
 
+# == Permission ==
+# Specify the format of the permission column, for local files only.
+# Possible values: rwx, octal
+permission: rwx
+
 # == Sorting ==
 sorting:
-  # Specify what to sort by.
-  # Possible values: extension, name, time, size, version
+  # Specify what to sort by. Several columns can be separated by commas, the
+  # next ones ordering the entries the previous ones leave equal, and a column
+  # prefixed with "-" is sorted in reverse, e.g. "content-type,-time".
+  # Possible values: extension, name, time, size, version, content-type, etag,
+  # path, none
   column: name
   # Whether to reverse the sorting.
   # Possible values: false, true
@@ -276,15 +499,102 @@ sorting:
   # Possible values: first, last, none
   dir-grouping: none
 
+# == No Symlink ==
+# Whether to omit showing symlink targets.
+# Possible values: false, true
+no-symlink: false
+
+# == Symlink arrow ==
+# Specifies how the symlink arrow display, chars in both ascii and utf8.
+symlink-arrow: ⇒
+
 # == Total size ==
 # Whether to display the total size of directories.
 # Possible values: false, true
 total-size: false
 
+# == Total size limit ==
+# This caps the walk done for the total size of directories. A directory which
+# can not be walked within the limit keeps its own size.
+total-size-limit:
+  # How deep to walk under each directory. Leave it unspecified for (virtually)
+  # infinite.
+  # depth: 3
+  # How many seconds walking each input may take. Leave it unspecified for no
+  # timeout.
+  # timeout: 30
+
 # == Hyperlink ==
 # Whether to display the total size of directories.
 # Possible values: always, auto, never
 hyperlink: never
+
+# == Concurrency ==
+# How many requests can be sent to the storage at the same time.
+# Possible values: any positive integer
+concurrency: 16
+
+# == S3 ==
+# The settings of the `s3://bucket/path` inputs, overridden by the --endpoint,
+# --region, --path-style, --anonymous and --aws-profile arguments.
+# "endpoint" is for S3 compatible services, like MinIO, Ceph or R2.
+# "region" is detected from the bucket when it is not set here, in the
+# environment or in `~/.aws/config`.
+# "path-style" puts the bucket in the path rather than in the host name, it
+# defaults to true with an endpoint and to false on AWS.
+# "anonymous" lists public buckets without signing the requests.
+# "credential" is like the one of the profiles below, the `AWS_*` environment
+# variables and `~/.aws/credentials` are used when it is not set.
+# s3:
+#   endpoint: http://localhost:9000
+#   region: us-east-1
+#   path-style: true
+#   anonymous: false
+#   credential:
+#     aws-profile: minio
+
+# == Profiles ==
+# Named storages, list them with `dl <name>:/path`.
+# "type" is the service to use: fs, s3, gcs, azblob, webdav, ...
+# "credential" is either "static", with the keys written here, "env", with
+# the names of the environment variables holding the keys, or "aws-profile",
+# with the name of a profile of `~/.aws/credentials`. WebDAV and HTTP servers
+# take a "basic" username and password, or a "bearer" token, Redis a "basic"
+# username and password.
+# "datadir" is the directory of a sled or rocksdb store, and "delimiter" the
+# character splitting the keys of key-value stores into directories, ":" for
+# Redis and "/" for the others by default. The Redis database is the first
+# directory of the listed path, like in `dl cache:/3/user/`.
+# "path-style" and "anonymous" are the ones of the S3 section above.
+# "blocks" and "date" override the display of the profile listings.
+# profiles:
+#   prod-logs:
+#     type: s3
+#     bucket: logs
+#     root: /prod
+#     endpoint: https://s3.amazonaws.com
+#     region: us-east-1
+#     credential:
+#       env:
+#         access-key-id: PROD_ACCESS_KEY_ID
+#         secret-access-key: PROD_SECRET_ACCESS_KEY
+#     blocks:
+#       - date
+#       - size
+#       - name
+#     date: relative
+#   artifacts:
+#     type: webdav
+#     endpoint: https://artifacts.local
+#     root: /dav
+#     credential:
+#       basic:
+#         username: ci
+#         password: secret
+#   cache:
+#     type: redis
+#     endpoint: tcp://cache.local:6379
+#     delimiter: "."
 "#;
 
 #[cfg(test)]
@@ -296,15 +606,19 @@ impl Config {
 
 #[cfg(test)]
 mod tests {
-    use super::Config;
+    use super::{Config, Credential};
+    use crate::app::Cli;
     use crate::config_file;
     use crate::flags::color::{ColorOption, ThemeOption};
+    use crate::flags::format::Format;
     use crate::flags::icons::{IconOption, IconTheme};
     use crate::flags::layout::Layout;
     use crate::flags::permission::PermissionFlag;
     use crate::flags::size::SizeFlag;
-    use crate::flags::sorting::{DirGrouping, SortColumn};
+    use crate::flags::sorting::DirGrouping;
     use crate::flags::HyperlinkOption;
+    use clap::Parser;
+    use std::path::Path;
 
     #[test]
     fn test_read_default() {
@@ -327,6 +641,9 @@ mod tests {
                 date: None,
                 dereference: Some(false),
                 display: None,
+                filters: None,
+                format: Some(Format::Text),
+                hide_dot_keys: Some(false),
                 icons: Some(config_file::Icons {
                     when: Some(IconOption::Auto),
                     theme: Some(IconTheme::Fancy),
@@ -342,15 +659,19 @@ mod tests {
                 size: Some(SizeFlag::Default),
                 permission: Some(PermissionFlag::Rwx),
                 sorting: Some(config_file::Sorting {
-                    column: Some(SortColumn::Name),
+                    column: Some("name".into()),
                     reverse: Some(false),
                     dir_grouping: Some(DirGrouping::None),
                 }),
                 no_symlink: Some(false),
                 total_size: Some(false),
+                total_size_limit: None,
                 symlink_arrow: Some("⇒".into()),
                 hyperlink: Some(HyperlinkOption::Never),
-                header: None
+                header: None,
+                concurrency: Some(16),
+                s3: None,
+                profiles: None
             },
             c
         );
@@ -378,4 +699,146 @@ mod tests {
     fn test_read_bad_display() {
         assert!(Config::from_yaml("display: bad").is_err())
     }
+
+    #[test]
+    fn test_read_profiles() {
+        let c = Config::from_yaml(
+            r#"---
+profiles:
+  prod-logs:
+    type: s3
+    bucket: logs
+    region: us-east-1
+    credential:
+      env:
+        access-key-id: PROD_AK
+        secret-access-key: PROD_SK
+    date: relative
+"#,
+        )
+        .unwrap();
+        let profile = &c.profiles.unwrap()["prod-logs"];
+        assert_eq!("s3", profile.service);
+        assert_eq!(Some("logs".into()), profile.bucket);
+        assert_eq!(Some("us-east-1".into()), profile.region);
+        assert_eq!(
+            Some(Credential::Env {
+                access_key_id: "PROD_AK".into(),
+                secret_access_key: "PROD_SK".into(),
+            }),
+            profile.credential
+        );
+        assert_eq!(Some("relative".into()), profile.date);
+    }
+
+    #[test]
+    fn test_read_http_credentials() {
+        let c = Config::from_yaml(
+            r#"---
+profiles:
+  dav:
+    type: webdav
+    endpoint: https://artifacts.local
+    credential:
+      basic:
+        username: ci
+        password: secret
+  releases:
+    type: https
+    endpoint: https://releases.local
+    credential:
+      bearer: TOKEN
+"#,
+        )
+        .unwrap();
+        let profiles = c.profiles.unwrap();
+        assert_eq!(
+            Some(Credential::Basic {
+                username: "ci".into(),
+                password: "secret".into(),
+            }),
+            profiles["dav"].credential
+        );
+        assert_eq!(
+            Some(Credential::Bearer("TOKEN".into())),
+            profiles["releases"].credential
+        );
+    }
+
+    #[test]
+    fn test_read_kv_profiles() {
+        let c = Config::from_yaml(
+            "profiles: {cache: {type: redis, delimiter: '.'}, app: {type: sled, datadir: /var/db}}",
+        )
+        .unwrap();
+        let profiles = c.profiles.unwrap();
+        assert_eq!(Some(".".into()), profiles["cache"].delimiter);
+        assert_eq!(Some("/var/db".into()), profiles["app"].datadir);
+    }
+
+    #[test]
+    fn test_read_profile_bad_field() {
+        assert!(Config::from_yaml("profiles: {p: {type: s3, bukket: logs}}").is_err())
+    }
+
+    #[test]
+    fn test_profile_of() {
+        let c = Config::from_yaml("profiles: {logs: {type: s3, bucket: logs}}").unwrap();
+        assert_eq!(
+            Some("logs"),
+            c.profile_of(Path::new("logs:/2023/")).map(|(name, _)| name)
+        );
+        assert_eq!(None, c.profile_of(Path::new("other:/2023/")));
+        assert_eq!(None, c.profile_of(Path::new("logs")));
+    }
+
+    #[test]
+    fn test_with_profile_overrides() {
+        let c = Config::from_yaml(
+            "{date: date, blocks: [name], profiles: {logs: {type: s3, date: relative}}}",
+        )
+        .unwrap()
+        .with_profile_overrides(&["logs:/2023/".into()]);
+        assert_eq!(Some("relative".into()), c.date);
+        assert_eq!(Some(vec!["name".into()]), c.blocks);
+    }
+
+    #[test]
+    fn test_read_s3() {
+        let c = Config::from_yaml(
+            r#"---
+s3:
+  endpoint: http://localhost:9000
+  path-style: true
+  credential:
+    aws-profile: minio
+"#,
+        )
+        .unwrap();
+        let s3 = c.s3.unwrap();
+        assert_eq!(Some("http://localhost:9000".into()), s3.endpoint);
+        assert_eq!(Some(true), s3.path_style);
+        assert_eq!(Some(Credential::AwsProfile("minio".into())), s3.credential);
+    }
+
+    #[test]
+    fn test_with_s3_overrides() {
+        let argv = ["lsd", "--endpoint", "http://minio:9000", "--anonymous"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let c = Config::from_yaml(
+            "{s3: {region: eu-west-1}, profiles: {a: {type: s3, endpoint: x}, b: {type: fs}}}",
+        )
+        .unwrap()
+        .with_s3_overrides(&cli);
+
+        let s3 = c.s3.unwrap();
+        assert_eq!(Some("http://minio:9000".into()), s3.endpoint);
+        assert_eq!(Some("eu-west-1".into()), s3.region);
+        assert_eq!(Some(true), s3.anonymous);
+        assert_eq!(None, s3.path_style);
+        let profiles = c.profiles.unwrap();
+        assert_eq!(Some("http://minio:9000".into()), profiles["a"].endpoint);
+        assert_eq!(Some(true), profiles["a"].anonymous);
+        assert_eq!(None, profiles["b"].endpoint);
+    }
 }
@@ -1,20 +1,27 @@
 use crate::color::Colors;
-use crate::dal::{Meta, DAL};
+use crate::dal::{Meta, Uri, DAL};
 use crate::display;
-use crate::flags::{ColorOption, Display, Flags, HyperlinkOption, Layout, SortOrder, ThemeOption};
+use crate::flags::{
+    ColorOption, DirGrouping, Display, Flags, Format, HyperlinkOption, Layout, SortOrder,
+    ThemeOption,
+};
 use crate::icon::Icons;
-use crate::{print_output, sort};
-use std::path::PathBuf;
+use crate::meta::name::DisplayOption;
+use crate::meta::Name;
+use crate::{print_error, print_output, record, sort, ExitCode};
 
-use std;
-#[cfg(not(target_os = "windows"))]
 use std::io;
 #[cfg(not(target_os = "windows"))]
 use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
 
 #[cfg(target_os = "windows")]
 use terminal_size::terminal_size;
 
+/// The number of entries of a [Batch], the size of a listing page of most object stores.
+const BATCH_SIZE: usize = 1000;
+
 pub struct Core {
     flags: Flags,
     icons: Icons,
@@ -80,65 +87,356 @@ impl Core {
         }
     }
 
-    pub async fn run(self, paths: Vec<PathBuf>) -> Result<(), Box<dyn std::error::Error>> {
-        let mut meta_list = self.fetch(paths).await?;
+    /// List and print the inputs at `uris`.
+    ///
+    /// An input which can not be listed is reported and skipped, which is a major issue. An
+    /// entry which can not be read under an input is a minor one, the rest is still printed.
+    pub async fn run(self, uris: Vec<Uri>) -> ExitCode {
+        if self.streams() {
+            return self.stream(uris).await;
+        }
+
+        let (meta_list, exit_code) = self.list(uris).await;
+        print_output!("{}", self.render(&meta_list));
+        exit_code
+    }
 
+    /// List the inputs at `uris`, with their entries in their `sub_metas` as deep as the
+    /// recursion flags go, sorted like they are displayed.
+    ///
+    /// The errors are reported like [run](Core::run) does, and the inputs which can not be
+    /// listed are left out.
+    pub async fn list(&self, uris: Vec<Uri>) -> (Vec<Meta>, ExitCode) {
+        let (mut meta_list, exit_code) = self.fetch(uris).await;
         self.sort(&mut meta_list);
-        self.display(&meta_list);
-        Ok(())
+        (meta_list, exit_code)
     }
 
-    async fn fetch(&self, paths: Vec<PathBuf>) -> io::Result<Vec<Meta>> {
-        let mut meta_list = Vec::with_capacity(paths.len());
-        let depth = match self.flags.layout {
-            Layout::Tree { .. } => self.flags.recursion.depth,
+    /// Render the listed `metas` like they are printed: as a grid, a tree, or records in the
+    /// format of the flags.
+    pub fn render(&self, metas: &[Meta]) -> String {
+        match self.flags.format {
+            Format::Json => record::json(metas, &self.flags),
+            Format::Ndjson => record::ndjson(metas, &self.flags),
+            Format::Csv => record::delimited(metas, &self.flags, ','),
+            Format::Tsv => record::delimited(metas, &self.flags, '\t'),
+            _ if self.flags.layout == Layout::Tree => {
+                display::tree(metas, &self.flags, &self.colors, &self.icons)
+            }
+            _ => display::grid(metas, &self.flags, &self.colors, &self.icons),
+        }
+    }
+
+    /// Render the name of `meta` as the path it is `shown` as, with the colors and the icons of
+    /// the listings.
+    pub fn render_path(&self, meta: &Meta, shown: &Path) -> String {
+        Name::new(shown, meta.file_type())
+            .render(
+                &self.colors,
+                &self.icons,
+                &DisplayOption::None,
+                self.flags.hyperlink,
+                self.flags.should_quote,
+            )
+            .to_string()
+    }
+
+    /// Whether the entries are printed as soon as they are listed, rather than once all of them
+    /// have been listed and sorted: with NDJSON, and with the text output of `-U`, unless the
+    /// tree or the total sizes need the whole listing. Any sort needs the whole listing too.
+    fn streams(&self) -> bool {
+        if self.flags.total_size.0 {
+            return false;
+        }
+        match self.flags.format {
+            Format::Ndjson => true,
+            Format::Text => {
+                self.flags.layout != Layout::Tree
+                    && self.flags.sorting.columns.is_none()
+                    && self.flags.sorting.dir_grouping == DirGrouping::None
+            }
+            _ => false,
+        }
+    }
+
+    /// Print the entries as soon as they are listed, without keeping the listing in memory.
+    ///
+    /// NDJSON prints each entry on its own line. The text output is printed by [Batch]es of
+    /// entries, in the order of the listing and aligned within each batch. Directories keep
+    /// their own size.
+    async fn stream(&self, uris: Vec<Uri>) -> ExitCode {
+        let work_dir = match std::env::current_dir() {
+            Ok(dir) => dir,
+            Err(err) => {
+                print_error!("current directory: {}.", err);
+                return ExitCode::MajorIssue;
+            }
+        };
+        let mut batch = Batch {
+            core: self,
+            flags: self.flags.clone(),
+            show_inputs: uris.len() > 1,
+            dir: None,
+            metas: Vec::with_capacity(BATCH_SIZE),
+        };
+        let mut exit_code = ExitCode::OK;
+        for uri in uris {
+            match self.stream_input(&work_dir, &uri, &mut batch).await {
+                Ok(code) => exit_code.set_if_greater(code),
+                Err(err) => {
+                    // What has been listed before the error is still printed.
+                    batch.flush();
+                    print_error!("{}: {}.", uri.path.display(), err);
+                    exit_code.set_if_greater(ExitCode::MajorIssue);
+                }
+            }
+        }
+        exit_code
+    }
+
+    /// Stream the input at `uri` into the `batch`, which fails when the input itself can not be
+    /// listed.
+    async fn stream_input(
+        &self,
+        work_dir: &Path,
+        uri: &Uri,
+        batch: &mut Batch<'_>,
+    ) -> io::Result<ExitCode> {
+        let dal = DAL::new(work_dir, uri, self.flags.concurrency.0)?;
+        let mut meta = dal.from_path(&uri.path).await?;
+        if self.flags.dereference.0 {
+            meta.dereference();
+        }
+
+        let expand = self.flags.layout != Layout::Tree
+            && self.flags.display != Display::DirectoryOnly
+            && meta.lists_content(&self.flags);
+        let recurse = (self.flags.layout == Layout::Tree
+            || self.flags.display != Display::DirectoryOnly)
+            && meta.lists_content(&self.flags);
+        if !expand {
+            batch.print_input(&meta);
+        }
+        if !recurse {
+            return Ok(ExitCode::OK);
+        }
+
+        batch.start(&uri.path);
+        let exit_code = dal
+            .walk(&meta, self.depth(), &self.flags, |dir, entry| {
+                batch.push(dir, entry)
+            })
+            .await?;
+        batch.flush();
+        Ok(exit_code)
+    }
+
+    fn depth(&self) -> usize {
+        match self.flags.layout {
+            Layout::Tree => self.flags.recursion.depth,
             _ if self.flags.recursion.enabled => self.flags.recursion.depth,
             _ => 1,
+        }
+    }
+
+    async fn fetch(&self, uris: Vec<Uri>) -> (Vec<Meta>, ExitCode) {
+        let mut meta_list = Vec::with_capacity(uris.len());
+        let mut exit_code = ExitCode::OK;
+        let work_dir = match std::env::current_dir() {
+            Ok(dir) => dir,
+            Err(err) => {
+                print_error!("current directory: {}.", err);
+                return (meta_list, ExitCode::MajorIssue);
+            }
         };
 
-        let work_dir = std::env::current_dir().unwrap().clone();
-        for path in paths {
-            let dal = DAL::new(&work_dir, &path);
-            let current = path;
-            let mut meta = dal.from_path(&current).await?;
-
-            let recurse =
-                self.flags.layout == Layout::Tree || self.flags.display != Display::DirectoryOnly;
-            if recurse {
-                let subs = dal.recurse_into(&meta, depth, &self.flags).await?;
-                meta.sub_metas = subs;
+        for uri in uris {
+            match self.fetch_input(&work_dir, &uri).await {
+                Ok((meta, code)) => {
+                    meta_list.push(meta);
+                    exit_code.set_if_greater(code);
+                }
+                Err(err) => {
+                    print_error!("{}: {}.", uri.path.display(), err);
+                    exit_code.set_if_greater(ExitCode::MajorIssue);
+                }
             }
-            meta_list.push(meta);
         }
-        // TODO(kw): calculate size
-        // // Only calculate the total size of a directory if it will be displayed
-        // if self.flags.total_size.0 && self.flags.blocks.displays_size() {
-        //     for meta in &mut meta_list.iter_mut() {
-        //         println!("size")
-        //         // meta.calculate_total_size();
-        //     }
-        // }
 
-        Ok(meta_list)
+        (meta_list, exit_code)
+    }
+
+    /// List the input at `uri`, which fails when the input itself can not be listed.
+    async fn fetch_input(&self, work_dir: &Path, uri: &Uri) -> io::Result<(Meta, ExitCode)> {
+        let depth = self.depth();
+        let dal = DAL::new(work_dir, uri, self.flags.concurrency.0)?;
+        let mut meta = dal.from_path(&uri.path).await?;
+        if self.flags.dereference.0 {
+            meta.dereference();
+        }
+
+        let mut exit_code = ExitCode::OK;
+        let recurse =
+            self.flags.layout == Layout::Tree || self.flags.display != Display::DirectoryOnly;
+        if recurse {
+            let (subs, code) = dal.recurse_into(&meta, depth, &self.flags).await?;
+            meta.sub_metas = subs;
+            exit_code = code;
+        }
+
+        // Only calculate the total size of a directory if it will be displayed, the JSON records
+        // always have the size.
+        let displays_size = self.flags.blocks.displays_size()
+            || matches!(self.flags.format, Format::Json | Format::Ndjson);
+        if self.flags.total_size.0 && displays_size {
+            let depth = if recurse { depth } else { 0 };
+            let deadline = self
+                .flags
+                .total_size_limit
+                .timeout
+                .map(|timeout| Instant::now() + timeout);
+            if let Err(err) = dal
+                .total_size(&mut meta, depth, &self.flags, deadline)
+                .await
+            {
+                print_error!("{}: {}.", uri.path.display(), err);
+                exit_code.set_if_greater(ExitCode::MinorIssue);
+            }
+        }
+        Ok((meta, exit_code))
     }
 
-    fn sort(&self, metas: &mut Vec<Meta>) {
+    fn sort(&self, metas: &mut [Meta]) {
         metas.sort_unstable_by(|a, b| sort::by_meta(&self.sorters, a, b));
 
-        // for meta in metas {
-        //     if let Some(ref mut content) = meta.content {
-        //         self.sort(content);
-        //     }
-        // }
+        for meta in metas {
+            self.sort(&mut meta.sub_metas);
+        }
     }
+}
 
-    fn display(&self, metas: &[Meta]) {
-        let output = if self.flags.layout == Layout::Tree {
-            display::tree(metas, &self.flags, &self.colors, &self.icons)
-        } else {
-            display::grid(metas, &self.flags, &self.colors, &self.icons)
-        };
+/// The entries being streamed by [Core::stream], printed once [BATCH_SIZE] of them have been
+/// listed or once their directory has been listed.
+struct Batch<'a> {
+    core: &'a Core,
+    /// The flags of the core, without the header once it has been printed.
+    flags: Flags,
+    /// Whether the path of each input is printed before its content, like the paths of the
+    /// sub directories are.
+    show_inputs: bool,
+    /// The directory the `metas` have been listed from.
+    dir: Option<PathBuf>,
+    metas: Vec<Meta>,
+}
 
+impl Batch<'_> {
+    /// Print an input which is not expanded into its content, like a file or a directory
+    /// with `-d`.
+    fn print_input(&mut self, meta: &Meta) {
+        self.flush();
+        if self.flags.format == Format::Ndjson {
+            print_output!("{}", record::ndjson_line(meta));
+            return;
+        }
+
+        let metas = std::slice::from_ref(meta);
+        let output = display::grid(metas, &self.flags, &self.core.colors, &self.core.icons);
         print_output!("{}", output);
+        self.flags.header.0 = false;
+    }
+
+    /// Start the listing of the input at `path`.
+    fn start(&mut self, path: &Path) {
+        self.flush();
+        if self.show_inputs && self.flags.format == Format::Text {
+            print_output!("\n{}:\n", path.display());
+        }
+        self.dir = None;
+    }
+
+    fn push(&mut self, dir: &Path, meta: Meta) {
+        if self.flags.format == Format::Ndjson {
+            print_output!("{}", record::ndjson_line(&meta));
+            return;
+        }
+
+        if self.dir.as_deref() != Some(dir) {
+            self.flush();
+            // The path of the input itself is printed by `start`.
+            if self.dir.is_some() {
+                print_output!("\n{}:\n", dir.display());
+            }
+            self.dir = Some(dir.to_path_buf());
+        } else if self.metas.len() == BATCH_SIZE {
+            self.flush();
+        }
+        self.metas.push(meta);
+    }
+
+    fn flush(&mut self) {
+        if self.metas.is_empty() {
+            return;
+        }
+
+        let output = display::entries(
+            &self.metas,
+            &self.flags,
+            &self.core.colors,
+            &self.core.icons,
+        );
+        print_output!("{}", output);
+        self.metas.clear();
+        self.flags.header.0 = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Core;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::dal::Uri;
+    use crate::flags::Flags;
+    use crate::ExitCode;
+
+    use assert_fs::prelude::*;
+    use clap::Parser;
+
+    #[tokio::test]
+    async fn test_list_and_render() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("small").write_str("0").unwrap();
+        dir.child("large").write_str("0123456789").unwrap();
+
+        let cli = Cli::try_parse_from(["lsd", "-1", "--sort", "-size", "--icon", "never"]).unwrap();
+        let config = Config::with_none();
+        let flags = Flags::configure_from(&cli, &config).unwrap();
+        let uri = Uri::parse(dir.path(), &config).unwrap();
+
+        let core = Core::new(flags);
+        let (metas, exit_code) = core.list(vec![uri]).await;
+        assert_eq!(ExitCode::OK, exit_code);
+        assert_eq!(1, metas.len());
+        assert_eq!(2, metas[0].sub_metas.len());
+        assert_eq!("small\nlarge\n", core.render(&metas));
+    }
+
+    #[test]
+    fn test_streams() {
+        for (argv, expected) in [
+            (&["lsd", "-1"][..], false),
+            (&["lsd", "-1U"], true),
+            (&["lsd", "-lU"], true),
+            (&["lsd", "-U", "--group-dirs", "first"], false),
+            (&["lsd", "-U", "--tree"], false),
+            (&["lsd", "--format", "ndjson"], true),
+            (&["lsd", "--format", "ndjson", "--total-size"], false),
+            (&["lsd", "--format", "csv", "-U"], false),
+        ] {
+            let cli = Cli::try_parse_from(argv).unwrap();
+            let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+            assert_eq!(expected, Core::new(flags).streams(), "{argv:?}");
+        }
     }
 }
@@ -1,184 +1,950 @@
-use crate::flags::{Display, Flags, Layout};
-use crate::meta::date::Date;
-use crate::meta::filetype::FileType;
-use crate::meta::name::Name;
-use crate::meta::size::Size;
+mod autoindex;
+mod content_type;
+#[cfg(any(feature = "redis", feature = "sled", feature = "rocksdb"))]
+mod kv;
+mod memory;
+mod uri;
 
+pub use self::memory::seed;
+pub use self::uri::Uri;
+
+use crate::app::ByteRange;
+use crate::flags::{Block, Display, Flags, Format, Layout, SortColumn};
+use crate::meta::{Date, FileType, INode, Links, Name, Owner, Permissions, Size, SymLink};
+use crate::{print_error, ExitCode};
+
+use std::collections::{HashMap, VecDeque};
+use std::env;
 use std::io::{self, Error, ErrorKind};
-use std::path::{Path, PathBuf};
-use std::time::SystemTime;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Instant, SystemTime};
+
+use self::autoindex::AutoindexLayer;
+use self::content_type::ContentTypeLayer;
+
+#[cfg(any(feature = "redis", feature = "sled", feature = "rocksdb"))]
+use opendal::{
+    raw::adapters::kv::{Adapter, Backend},
+    OperatorBuilder,
+};
+
+use flagset::FlagSet;
 
-use futures::TryStreamExt;
+use futures::future::{self, BoxFuture};
+use futures::stream::{self, StreamExt};
+use futures::{FutureExt, TryStreamExt};
 
-use opendal::layers::RetryLayer;
-use opendal::Operator;
-use opendal::{services, Metadata};
+use opendal::layers::{ConcurrentLimitLayer, RetryLayer};
+use opendal::ops::OpWrite;
+use opendal::{
+    services, Builder, Entry, EntryMode, Metadata, Metakey, Operator, Reader, Scheme, Writer,
+};
 
 pub struct DAL {
     op: Operator,
+    /// The root of the local filesystem served by `op`, [None] for the other services.
+    local_root: Option<PathBuf>,
 }
 
 impl DAL {
-    pub fn new(work_dir: &Path, path: &Path) -> Self {
-        let wd = if path.is_relative() {
-            work_dir.to_path_buf()
-        } else {
-            Path::new("/").to_path_buf()
-        };
+    /// Build the [Operator] serving the given [Uri].
+    ///
+    /// Relative local paths are resolved from `work_dir`. The rest of the service configuration,
+    /// like credentials or regions, comes from the profile of the [Uri], or else from the
+    /// `OPENDAL_<SCHEME>_<KEY>` environment variables, e.g. `OPENDAL_S3_REGION`.
+    ///
+    /// At most `concurrency` requests are sent to the service at the same time.
+    pub fn new(work_dir: &Path, uri: &Uri, concurrency: usize) -> io::Result<Self> {
+        let mut config = config_from_env(uri.scheme);
+        config.extend(uri.options.clone());
+        if let Some(authority) = &uri.authority {
+            match uri.scheme {
+                Scheme::Azblob => config.insert("container".into(), authority.clone()),
+                Scheme::Azdfs => config.insert("filesystem".into(), authority.clone()),
+                Scheme::Gcs | Scheme::Obs | Scheme::Oss | Scheme::S3 => {
+                    config.insert("bucket".into(), authority.clone())
+                }
+                _ => None,
+            };
+        }
+        if let Some(endpoint) = uri.endpoint() {
+            config.insert("endpoint".into(), endpoint);
+        }
 
-        let mut builder = services::Fs::default();
-        builder.root(wd.to_str().unwrap());
-        let op: Operator = Operator::new(builder)
-            .unwrap()
-            .layer(RetryLayer::new())
-            .finish();
+        let mut local_root = None;
+        let op = match uri.scheme {
+            Scheme::Fs => {
+                let root = if uri.path.is_relative() {
+                    work_dir
+                } else {
+                    Path::new("/")
+                };
+                let root = config
+                    .entry("root".into())
+                    .or_insert_with(|| root.to_string_lossy().to_string());
+                local_root = Some(PathBuf::from(root.as_str()));
+                build::<services::Fs>(config, concurrency)?
+            }
+            // Shared by the whole process, so that what has been seeded can be listed.
+            Scheme::Memory => memory::operator(),
+            Scheme::S3 => {
+                s3_config(&mut config);
+                build::<services::S3>(config, concurrency)?
+            }
+            Scheme::Gcs => build::<services::Gcs>(config, concurrency)?,
+            Scheme::Oss => build::<services::Oss>(config, concurrency)?,
+            Scheme::Obs => build::<services::Obs>(config, concurrency)?,
+            Scheme::Azblob => build::<services::Azblob>(config, concurrency)?,
+            Scheme::Azdfs => build::<services::Azdfs>(config, concurrency)?,
+            // The service can not list, the directories are read from their index pages.
+            Scheme::Http => {
+                let autoindex = AutoindexLayer::new(&config)?;
+                build::<services::Http>(config, concurrency)?.layer(autoindex)
+            }
+            Scheme::Ipmfs => build::<services::Ipmfs>(config, concurrency)?,
+            Scheme::Webdav => build::<services::Webdav>(config, concurrency)?,
+            Scheme::Webhdfs => build::<services::Webhdfs>(config, concurrency)?,
+            #[cfg(feature = "redis")]
+            Scheme::Redis => build_kv(kv::Redis::new(&config)?, &config, concurrency),
+            #[cfg(feature = "sled")]
+            Scheme::Sled => build_kv(kv::Sled::new(&config)?, &config, concurrency),
+            #[cfg(feature = "rocksdb")]
+            Scheme::Rocksdb => build_kv(kv::Rocksdb::new(&config)?, &config, concurrency),
+            scheme => {
+                return Err(Error::new(
+                    ErrorKind::Unsupported,
+                    format!("unsupported scheme {scheme}"),
+                ))
+            }
+        };
 
-        DAL { op }
+        Ok(DAL { op, local_root })
     }
 
     pub async fn from_path(&self, path: &Path) -> io::Result<Meta> {
+        let p = path.to_string_lossy();
+        let local = self.local(path);
+        let stat = match self.op.stat(&p).await {
+            // Object storages have no real directories, a prefix can only be found with a
+            // trailing `/`, so `s3://bucket/logs` should be looked up as `logs/`.
+            Err(err)
+                if err.kind() == opendal::ErrorKind::NotFound
+                    && self.op.info().scheme() != Scheme::Fs
+                    && !p.ends_with('/') =>
+            {
+                self.op.stat(&format!("{p}/")).await
+            }
+            res => res,
+        };
+        let (meta, keys) = match stat {
+            Ok(meta) => (meta, Metakey::Complete.into()),
+            // OpenDAL follows the links, only the local filesystem knows about the broken ones.
+            Err(_) if local.as_ref().is_some_and(Local::is_broken_link) => {
+                (Metadata::new(EntryMode::Unknown), Metakey::Mode.into())
+            }
+            Err(err) => return Err(err.into()),
+        };
+        let (meta, keys) = self.with_seeded(&p, meta, keys);
+
         Ok(Meta {
             path: path.to_path_buf(),
-            meta: self.op.stat(path.to_str().unwrap()).await?,
+            meta,
+            keys,
+            local,
+            total_size: None,
+            alias: None,
             sub_metas: vec![],
         })
     }
 
-    pub async fn recurse_into(
+    /// What the local filesystem knows about `path`, for the blocks only it knows about, like
+    /// the permissions or the owner, and for the symbolic links.
+    fn local(&self, path: &Path) -> Option<Local> {
+        let root = self.local_root.as_ref()?;
+        let path = root.join(path.strip_prefix("/").unwrap_or(path));
+        let meta = path.symlink_metadata().ok()?;
+        if !meta.file_type().is_symlink() {
+            return Some(Local {
+                meta,
+                target: None,
+                symlink: None,
+            });
+        }
+
+        Some(Local {
+            meta,
+            target: path.metadata().ok(),
+            symlink: Some(SymLink::from(path.as_path())),
+        })
+    }
+
+    /// Check whether `path` is a directory, or a prefix with objects under it.
+    ///
+    /// Unlike [from_path](DAL::from_path), a missing prefix is not taken for an empty directory.
+    pub async fn is_dir(&self, path: &Path) -> bool {
+        let p = path.to_string_lossy();
+        match self.op.stat(&p).await {
+            Ok(meta) => meta.is_dir(),
+            Err(_) => match self.op.list(&format!("{}/", p.trim_end_matches('/'))).await {
+                Ok(mut lister) => matches!(lister.try_next().await, Ok(Some(_))),
+                Err(_) => false,
+            },
+        }
+    }
+
+    /// Read the whole content of the file at `path`.
+    pub async fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        Ok(self.op.read(&path.to_string_lossy()).await?)
+    }
+
+    /// Write `content` to the file at `path`, with the given content type if any.
+    pub async fn write(
         &self,
-        src: &Meta,
+        path: &Path,
+        content: Vec<u8>,
+        content_type: Option<&str>,
+    ) -> io::Result<()> {
+        let mut args = OpWrite::new();
+        if let Some(content_type) = content_type {
+            args = args.with_content_type(content_type);
+        }
+        Ok(self
+            .op
+            .write_with(&path.to_string_lossy(), args, content)
+            .await?)
+    }
+
+    /// Remove the files at `paths`, in batches when the service supports it.
+    pub async fn remove(&self, paths: &[PathBuf]) -> io::Result<()> {
+        let paths = paths
+            .iter()
+            .map(|path| path.to_string_lossy().to_string())
+            .collect();
+        Ok(self.op.remove(paths).await?)
+    }
+
+    /// Remove the directory at `path`, which has to be empty on services with real directories.
+    pub async fn remove_dir(&self, path: &Path) -> io::Result<()> {
+        let p = path.to_string_lossy();
+        Ok(self
+            .op
+            .delete(&format!("{}/", p.trim_end_matches('/')))
+            .await?)
+    }
+
+    /// Open a writer appending to the file at `path`, with the given content type if any.
+    ///
+    /// Not every service can append.
+    pub async fn writer(&self, path: &Path, content_type: Option<&str>) -> io::Result<Writer> {
+        let op = match content_type {
+            Some(content_type) => self
+                .op
+                .clone()
+                .layer(ContentTypeLayer(content_type.to_string())),
+            None => self.op.clone(),
+        };
+        Ok(op.writer(&path.to_string_lossy()).await?)
+    }
+
+    /// Open a reader on the content of the file at `path`, or only on the bytes in `range`.
+    pub async fn reader(&self, path: &Path, range: Option<ByteRange>) -> io::Result<Reader> {
+        let p = path.to_string_lossy();
+        let reader = match range {
+            None => self.op.reader(&p).await?,
+            Some(ByteRange::Bounded(start, end)) => self.op.range_reader(&p, start..=end).await?,
+            Some(ByteRange::From(start)) => self.op.range_reader(&p, start..).await?,
+            Some(ByteRange::Suffix(length)) => {
+                let size = self.op.stat(&p).await?.content_length();
+                self.op
+                    .range_reader(&p, size.saturating_sub(length)..)
+                    .await?
+            }
+        };
+        Ok(reader)
+    }
+
+    /// List the content of `src`, and recursively the content of its sub directories, until
+    /// `depth` levels have been listed. The content of each directory is kept in its
+    /// [sub_metas](Meta::sub_metas).
+    ///
+    /// Only the listing of `src` itself fails. An entry, or a sub directory, which can not be
+    /// read is reported and skipped, which is a minor issue of the returned [ExitCode].
+    pub fn recurse_into<'a>(
+        &'a self,
+        src: &'a Meta,
         depth: usize,
-        flags: &Flags,
-    ) -> io::Result<Vec<Meta>> {
-        if depth == 0 {
-            return Ok(vec![]);
+        flags: &'a Flags,
+    ) -> BoxFuture<'a, io::Result<(Vec<Meta>, ExitCode)>> {
+        async move {
+            if depth == 0 {
+                return Ok((vec![], ExitCode::OK));
+            }
+            if flags.display == Display::DirectoryOnly && flags.layout != Layout::Tree {
+                return Ok((vec![], ExitCode::OK));
+            }
+
+            if !src.lists_content(flags) {
+                return Ok((vec![], ExitCode::OK));
+            }
+
+            let keys = metakeys(flags);
+            // Drain the listing before visiting the entries, so that the pager does not hold
+            // one of the concurrency permits the sub directories are waiting for.
+            let entries: Vec<Entry> = self
+                .op
+                .list(format!("{}/", src.path.to_string_lossy()).as_str())
+                .await?
+                .try_collect()
+                .await?;
+            let listed: Vec<(Option<Meta>, ExitCode)> = stream::iter(entries)
+                .map(|de| async move {
+                    let path = de.path().to_string();
+                    let mut entry = match self.entry(de, keys, flags).await {
+                        Ok(Some(entry)) => entry,
+                        Ok(None) => return (None, ExitCode::OK),
+                        Err(err) => {
+                            print_error!("{}: {}.", path, err);
+                            return (None, ExitCode::MinorIssue);
+                        }
+                    };
+                    match self.recurse_into(&entry, depth - 1, flags).await {
+                        Ok((sub_metas, exit_code)) => {
+                            entry.sub_metas = sub_metas;
+                            (Some(entry), exit_code)
+                        }
+                        Err(err) => {
+                            print_error!("{}: {}.", entry.path.display(), err);
+                            (Some(entry), ExitCode::MinorIssue)
+                        }
+                    }
+                })
+                .buffered(flags.concurrency.0)
+                .collect()
+                .await;
+
+            let mut exit_code = ExitCode::OK;
+            let mut metas = Vec::with_capacity(listed.len());
+            for (meta, code) in listed {
+                exit_code.set_if_greater(code);
+                metas.extend(meta);
+            }
+            metas.extend(self.dot_entries(&src.path, flags).await?);
+            Ok((metas, exit_code))
         }
-        if flags.display == Display::DirectoryOnly && flags.layout != Layout::Tree {
+        .boxed()
+    }
+
+    /// The `.` and `..` entries of the directory at `path`, shown with `-a` but not in the tree.
+    /// Only the local filesystem has them, object stores have nothing but keys.
+    async fn dot_entries(&self, path: &Path, flags: &Flags) -> io::Result<Vec<Meta>> {
+        if flags.display != Display::All
+            || flags.layout == Layout::Tree
+            || self.local_root.is_none()
+        {
             return Ok(vec![]);
         }
 
-        if src.file_type() == FileType::File {
-            return Ok(vec![]);
+        // `..` is given a path like the listed entries, without a leading `./` or `/`.
+        let parent = path.join("..");
+        let parent = parent
+            .strip_prefix("./")
+            .or_else(|_| parent.strip_prefix("/"))
+            .unwrap_or(&parent);
+        let mut entries = vec![];
+        for (alias, path) in [(".", path), ("..", parent)] {
+            let mut entry = self.from_path(path).await?;
+            entry.alias = Some(alias);
+            entries.push(entry);
         }
+        Ok(entries)
+    }
 
-        let mut subs: Vec<Meta> = Vec::new();
-        let mut ds = self
-            .op
-            .list(format!("{}/", src.path.to_string_lossy()).as_str())
-            .await?;
-        while let Some(de) = ds.try_next().await? {
-            let path = Path::new(de.path().clone());
-            let entry = self.from_path(path).await?;
-            let name = path
-                .file_name()
-                .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "invalid file name"))?;
-            if flags.ignore_globs.0.is_match(name) {
+    /// Visit the content of `src`, and of its sub directories, until `depth` levels have been
+    /// visited, without keeping the entries in memory. Each entry is given with the path of the
+    /// directory it has been listed from.
+    ///
+    /// The directories are visited level by level, reading one listing at a time, and the
+    /// entries are visited in the order of the listing, the `.` and `..` ones last.
+    ///
+    /// Like with [recurse_into](DAL::recurse_into), only the listing of `src` itself fails, the
+    /// entries and the sub directories which can not be read are reported and skipped.
+    pub async fn walk<F>(
+        &self,
+        src: &Meta,
+        depth: usize,
+        flags: &Flags,
+        mut visit: F,
+    ) -> io::Result<ExitCode>
+    where
+        F: FnMut(&Path, Meta),
+    {
+        let keys = metakeys(flags);
+        let mut exit_code = ExitCode::OK;
+        let mut dirs = VecDeque::from([(src.path.clone(), depth)]);
+        while let Some((dir, depth)) = dirs.pop_front() {
+            if depth == 0 {
                 continue;
             }
-            // skip files for --tree -d
-            if flags.layout == Layout::Tree
-                && flags.display == Display::DirectoryOnly
-                && entry.file_type() == FileType::Directory
-            {
-                continue;
+
+            let listed = self.walk_dir(&dir, depth, keys, flags, &mut dirs, &mut visit);
+            match listed.await {
+                Ok(code) => exit_code.set_if_greater(code),
+                Err(err) if dir == src.path => return Err(err),
+                Err(err) => {
+                    print_error!("{}: {}.", dir.display(), err);
+                    exit_code.set_if_greater(ExitCode::MinorIssue);
+                }
             }
+        }
+        Ok(exit_code)
+    }
 
-            subs.push(entry);
-        }
-        Ok(subs)
-    }
-
-    // pub fn calculate_total_size(&mut self) {
-    //     if self.size.is_none() {
-    //         return;
-    //     }
-    //
-    //     if let FileType::Directory { .. } = self.file_type {
-    //         if let Some(metas) = &mut self.content {
-    //             let mut size_accumulated = match &self.size {
-    //                 Some(size) => size.get_bytes(),
-    //                 None => 0,
-    //             };
-    //             for x in &mut metas.iter_mut() {
-    //                 x.calculate_total_size();
-    //                 size_accumulated += match &x.size {
-    //                     Some(size) => size.get_bytes(),
-    //                     None => 0,
-    //                 };
-    //             }
-    //             self.size = Some(Size::new(size_accumulated));
-    //         } else {
-    //             // possibility that 'depth' limited the recursion in 'recurse_into'
-    //             self.size = Some(Size::new(Meta::calculate_total_file_size(&self.path)));
-    //         }
-    //     }
-    // }
-
-    // fn calculate_total_file_size(path: &Path) -> u64 {
-    //     let metadata = path.symlink_metadata();
-    //     let metadata = match metadata {
-    //         Ok(meta) => meta,
-    //         Err(err) => {
-    //             print_error!("{}: {}.", path.display(), err);
-    //             return 0;
-    //         }
-    //     };
-    //     let file_type = metadata.file_type();
-    //     if file_type.is_file() {
-    //         metadata.len()
-    //     } else if file_type.is_dir() {
-    //         let mut size = metadata.len();
-    //
-    //         let entries = match path.read_dir() {
-    //             Ok(entries) => entries,
-    //             Err(err) => {
-    //                 print_error!("{}: {}.", path.display(), err);
-    //                 return size;
-    //             }
-    //         };
-    //         for entry in entries {
-    //             let path = match entry {
-    //                 Ok(entry) => entry.path(),
-    //                 Err(err) => {
-    //                     print_error!("{}: {}.", path.display(), err);
-    //                     continue;
-    //                 }
-    //             };
-    //             size += Meta::calculate_total_file_size(&path);
-    //         }
-    //         size
-    //     } else {
-    //         0
-    //     }
-    // }
+    /// Visit the content of `dir` for [walk](DAL::walk), queuing its sub directories in `dirs`.
+    /// This fails when `dir` can not be listed, the entries which can not be read are reported.
+    async fn walk_dir<F>(
+        &self,
+        dir: &Path,
+        depth: usize,
+        keys: FlagSet<Metakey>,
+        flags: &Flags,
+        dirs: &mut VecDeque<(PathBuf, usize)>,
+        visit: &mut F,
+    ) -> io::Result<ExitCode>
+    where
+        F: FnMut(&Path, Meta),
+    {
+        let mut exit_code = ExitCode::OK;
+        let mut entries = self
+            .op
+            .list(format!("{}/", dir.to_string_lossy()).as_str())
+            .await?
+            .map_err(io::Error::from)
+            // Only the listing errors stop the stream, the entry errors are kept in its items.
+            .map_ok(|de| async move {
+                let path = de.path().to_string();
+                let entry = self.entry(de, keys, flags).await;
+                Ok::<_, io::Error>(entry.map_err(|err| (path, err)))
+            })
+            .try_buffered(flags.concurrency.0);
+        while let Some(entry) = entries.try_next().await? {
+            let entry = match entry {
+                Ok(Some(entry)) => entry,
+                Ok(None) => continue,
+                Err((path, err)) => {
+                    print_error!("{}: {}.", path, err);
+                    exit_code.set_if_greater(ExitCode::MinorIssue);
+                    continue;
+                }
+            };
+            // Links are not followed, unless dereferenced, so that the walk stays under `src`.
+            if matches!(entry.file_type(), FileType::Directory { .. }) {
+                dirs.push_back((entry.path.clone(), depth - 1));
+            }
+            visit(dir, entry);
+        }
+        for entry in self.dot_entries(dir, flags).await? {
+            visit(dir, entry);
+        }
+        Ok(exit_code)
+    }
+
+    /// Build the [Meta] of a listed entry, or [None] when the entry should not be displayed.
+    async fn entry(
+        &self,
+        de: Entry,
+        keys: FlagSet<Metakey>,
+        flags: &Flags,
+    ) -> io::Result<Option<Meta>> {
+        let path = Path::new(de.path());
+        let name = path
+            .file_name()
+            .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "invalid file name"))?;
+        if flags.ignore_globs.0.is_match(name) {
+            return Ok(None);
+        }
+        if name.to_string_lossy().starts_with('.') && self.hides_dot_entries(flags) {
+            return Ok(None);
+        }
+
+        // Only stat the entry when the listing did not return the needed keys.
+        // Directories are listed as complete while carrying nothing but their mode.
+        let local = self.local(path);
+        let mut keys = keys;
+        let meta = match self.op.metadata(&de, keys).await {
+            Ok(meta) if meta.is_dir() && keys != Metakey::Mode => self.op.stat(de.path()).await?,
+            Ok(meta) => meta,
+            // OpenDAL follows the links, only the local filesystem knows about the broken ones.
+            Err(_) if local.as_ref().is_some_and(Local::is_broken_link) => {
+                keys = Metakey::Mode.into();
+                Metadata::new(EntryMode::Unknown)
+            }
+            Err(err) => return Err(err.into()),
+        };
+        let (meta, keys) = self.with_seeded(de.path(), meta, keys);
+        let mut entry = Meta {
+            path: path.to_path_buf(),
+            meta,
+            keys,
+            local,
+            total_size: None,
+            alias: None,
+            sub_metas: vec![],
+        };
+        if flags.dereference.0 {
+            entry.dereference();
+        }
+        // skip files for --tree -d
+        if flags.layout == Layout::Tree
+            && flags.display == Display::DirectoryOnly
+            && !entry.file_type().is_dirlike()
+        {
+            return Ok(None);
+        }
+        // Only the files are filtered, the directories are kept to list what is under them.
+        let filters = &flags.filters;
+        if (filters.by_size() || filters.by_time()) && !entry.file_type().is_dirlike() {
+            let bytes = entry.size().map(|s| s.get_bytes());
+            let modified = match entry.modified_date() {
+                Date::Date(modified) => Some(modified),
+                Date::Invalid => None,
+            };
+            if !filters.matches(bytes, modified) {
+                return Ok(None);
+            }
+        }
+
+        Ok(Some(entry))
+    }
+
+    /// The metadata of `path` with what has been [seed]ed into the `memory` service, which only
+    /// keeps the content of the files, and the keys it has.
+    fn with_seeded(
+        &self,
+        path: &str,
+        meta: Metadata,
+        keys: FlagSet<Metakey>,
+    ) -> (Metadata, FlagSet<Metakey>) {
+        match self.op.info().scheme() {
+            Scheme::Memory => memory::with_seeded(path, meta, keys),
+            _ => (meta, keys),
+        }
+    }
+
+    /// Whether the entries starting with a `.` are hidden: unless `-a` or `-A` is passed, the
+    /// dotfiles of the local filesystem are, and the keys of the other services only when
+    /// [HideDotKeys](crate::flags::HideDotKeys) is set.
+    fn hides_dot_entries(&self, flags: &Flags) -> bool {
+        matches!(flags.display, Display::VisibleOnly | Display::DirectoryOnly)
+            && (self.local_root.is_some() || flags.hide_dot_keys.0)
+    }
+
+    /// Set the total size of `meta` and of the directories in its `sub_metas`, which have been
+    /// filled by [recurse_into](DAL::recurse_into) with the same `depth`.
+    ///
+    /// The storage under `meta` is walked once, including what the listing hides or filters
+    /// out, so that a directory has the same total whether it is listed or not. Each directory
+    /// sums what is under it as deep as the [TotalSizeLimit](crate::flags::TotalSizeLimit)
+    /// allows. The walk fails with [ErrorKind::TimedOut] once the `deadline` has passed.
+    pub async fn total_size(
+        &self,
+        meta: &mut Meta,
+        depth: usize,
+        flags: &Flags,
+        deadline: Option<Instant>,
+    ) -> io::Result<()> {
+        if !matches!(meta.file_type(), FileType::Directory { .. }) {
+            return Ok(());
+        }
+
+        let walk = Walk {
+            limit: flags.total_size_limit.depth.unwrap_or(usize::MAX),
+            concurrency: flags.concurrency.0,
+            deadline,
+            totals: Mutex::new(HashMap::new()),
+        };
+        // The deepest listed directories are walked as deep as the limit from them.
+        let levels = self
+            .walk_size(&meta.path, depth.saturating_add(walk.limit), depth, &walk)
+            .await?;
+        let own = meta.size().map_or(0, |s| s.get_bytes());
+        meta.total_size = Some(own + levels.iter().take(walk.limit).sum::<u64>());
+
+        let totals = walk
+            .totals
+            .into_inner()
+            .unwrap_or_else(|err| err.into_inner());
+        set_total_sizes(&mut meta.sub_metas, &totals);
+        Ok(())
+    }
+
+    /// Sum the sizes of everything under `path`, `depth` levels deep, by level: the first
+    /// element sums the entries right under `path`.
+    ///
+    /// The totals of the directories found in the `listed` levels are kept in the [Walk].
+    fn walk_size<'a>(
+        &'a self,
+        path: &'a Path,
+        depth: usize,
+        listed: usize,
+        walk: &'a Walk,
+    ) -> BoxFuture<'a, io::Result<Vec<u64>>> {
+        async move {
+            if depth == 0 {
+                return Ok(vec![]);
+            }
+
+            let entries: Vec<Entry> = self
+                .op
+                .list(format!("{}/", path.to_string_lossy()).as_str())
+                .await?
+                .try_collect()
+                .await?;
+            stream::iter(entries)
+                .map(|de| async move {
+                    if matches!(walk.deadline, Some(deadline) if Instant::now() > deadline) {
+                        return Err(Error::new(
+                            ErrorKind::TimedOut,
+                            "walking for the total size timed out",
+                        ));
+                    }
+
+                    let mut meta = self
+                        .op
+                        .metadata(&de, Metakey::Mode | Metakey::ContentLength)
+                        .await?;
+                    if meta.is_dir() {
+                        meta = self.op.stat(de.path()).await?;
+                    }
+                    let (meta, _) = self.with_seeded(de.path(), meta, Metakey::Complete.into());
+                    let size = meta.content_length();
+                    if !meta.is_dir() {
+                        return Ok((size, vec![]));
+                    }
+
+                    let path = Path::new(de.path());
+                    let levels = self
+                        .walk_size(path, depth - 1, listed.saturating_sub(1), walk)
+                        .await?;
+                    if listed > 0 {
+                        let total = size + levels.iter().take(walk.limit).sum::<u64>();
+                        let mut totals = walk.totals.lock().unwrap_or_else(|err| err.into_inner());
+                        totals.insert(path.to_path_buf(), total);
+                    }
+                    Ok((size, levels))
+                })
+                .buffered(walk.concurrency)
+                .try_fold(vec![], |mut levels: Vec<u64>, (size, sub_levels)| {
+                    if levels.len() < sub_levels.len() + 1 {
+                        levels.resize(sub_levels.len() + 1, 0);
+                    }
+                    levels[0] += size;
+                    for (level, sub_size) in levels[1..].iter_mut().zip(sub_levels) {
+                        *level += sub_size;
+                    }
+                    future::ok(levels)
+                })
+                .await
+        }
+        .boxed()
+    }
+}
+
+/// The state of the walk done by [DAL::total_size].
+struct Walk {
+    /// How many levels each directory sums.
+    limit: usize,
+    concurrency: usize,
+    deadline: Option<Instant>,
+    /// The total sizes of the listed directories, by path.
+    totals: Mutex<HashMap<PathBuf, u64>>,
+}
+
+/// Set the `totals` found by [DAL::total_size] to the directories of `metas`.
+/// The form of `path` in the paths of the entries listed under it: relative to the root of the
+/// service, and without the `.` components.
+pub fn listed_path(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|c| !matches!(c, Component::CurDir | Component::RootDir))
+        .collect()
+}
+
+fn set_total_sizes(metas: &mut [Meta], totals: &HashMap<PathBuf, u64>) {
+    for meta in metas {
+        if matches!(meta.file_type(), FileType::Directory { .. }) {
+            meta.total_size = totals.get(&meta.path).copied();
+        }
+        set_total_sizes(&mut meta.sub_metas, totals);
+    }
+}
+
+/// Build an [Operator] for the service `B`, with the layers shared by all services.
+///
+/// A pager holds its permit until it is dropped, one more permit is given so that the entries of
+/// the listing being read can still be stat with the full `concurrency`.
+fn build<B: Builder>(config: HashMap<String, String>, concurrency: usize) -> io::Result<Operator> {
+    Ok(Operator::from_map::<B>(config)?
+        .layer(ConcurrentLimitLayer::new(concurrency + 1))
+        .layer(RetryLayer::new())
+        .finish())
+}
+
+/// Build an [Operator] for a key-value store of the [kv] module, with the same layers as [build].
+#[cfg(any(feature = "redis", feature = "sled", feature = "rocksdb"))]
+fn build_kv<A: Adapter>(
+    adapter: A,
+    config: &HashMap<String, String>,
+    concurrency: usize,
+) -> Operator {
+    let root = config.get("root").map_or("/", String::as_str);
+    OperatorBuilder::new(Backend::new(adapter).with_root(root))
+        .layer(ConcurrentLimitLayer::new(concurrency + 1))
+        .layer(RetryLayer::new())
+        .finish()
+}
+
+/// Turn the `path_style` and `anonymous` settings of a [Uri] into the configuration of the S3
+/// service.
+///
+/// The bucket is addressed in the path of custom endpoints, like MinIO or Ceph, and in the host
+/// name on AWS, unless `path_style` says otherwise. Anonymous requests are not signed, neither
+/// the `AWS_*` environment variables nor `~/.aws` are read then.
+fn s3_config(config: &mut HashMap<String, String>) {
+    let enabled = |value: Option<String>| value.map(|v| v == "true" || v == "on");
+    let path_style =
+        enabled(config.remove("path_style")).unwrap_or_else(|| config.contains_key("endpoint"));
+    if !path_style {
+        config
+            .entry("enable_virtual_host_style".into())
+            .or_insert_with(|| "true".into());
+    }
+    if enabled(config.remove("anonymous")) == Some(true) {
+        for key in ["access_key_id", "secret_access_key", "security_token"] {
+            config.remove(key);
+        }
+        config.insert("disable_config_load".into(), "true".into());
+    }
+}
+
+/// The metadata keys needed to display and sort the listed entries.
+fn metakeys(flags: &Flags) -> FlagSet<Metakey> {
+    let mut keys: FlagSet<Metakey> = Metakey::Mode.into();
+    if flags
+        .blocks
+        .0
+        .iter()
+        .any(|b| matches!(b, Block::Size | Block::SizeValue))
+        || flags.sorting.columns.contains(SortColumn::Size)
+        || flags.filters.by_size()
+    {
+        keys |= Metakey::ContentLength;
+    }
+    if flags.blocks.0.contains(&Block::Date)
+        || flags.sorting.columns.contains(SortColumn::Time)
+        || flags.filters.by_time()
+    {
+        keys |= Metakey::LastModified;
+    }
+    if flags.sorting.columns.contains(SortColumn::ContentType) {
+        keys |= Metakey::ContentType;
+    }
+    if flags.sorting.columns.contains(SortColumn::Etag) {
+        keys |= Metakey::Etag;
+    }
+    for block in &flags.blocks.0 {
+        match block {
+            Block::ContentType => keys |= Metakey::ContentType,
+            Block::Etag => keys |= Metakey::Etag,
+            Block::ContentMd5 => keys |= Metakey::ContentMd5,
+            Block::ContentDisposition => keys |= Metakey::ContentDisposition,
+            _ => {}
+        }
+    }
+    if matches!(flags.format, Format::Json | Format::Ndjson) {
+        keys |=
+            Metakey::ContentLength | Metakey::LastModified | Metakey::ContentType | Metakey::Etag;
+    }
+    keys
+}
+
+/// Collect the `OPENDAL_<SCHEME>_<KEY>` environment variables into a service configuration.
+fn config_from_env(scheme: Scheme) -> HashMap<String, String> {
+    let prefix = format!("opendal_{scheme}_");
+    env::vars()
+        .filter_map(|(k, v)| {
+            k.to_lowercase()
+                .strip_prefix(&prefix)
+                .map(|k| (k.to_string(), v))
+        })
+        .collect()
 }
 
 #[derive(Debug)]
 pub struct Meta {
     pub path: PathBuf,
     meta: Metadata,
+    /// The keys of `meta` which have been fetched, other keys must not be read.
+    keys: FlagSet<Metakey>,
+    /// What the local filesystem knows about the file, only for the `fs` service.
+    local: Option<Local>,
+    /// The size of a directory and everything under it, see [DAL::total_size].
+    total_size: Option<u64>,
+    /// The name shown instead of the one of `path`, for the `.` and `..` entries.
+    alias: Option<&'static str>,
     pub sub_metas: Vec<Meta>,
 }
 
+/// The metadata of a file on the local filesystem.
+#[derive(Debug)]
+struct Local {
+    /// The metadata of the file itself, or of the target of the link once dereferenced.
+    meta: std::fs::Metadata,
+    /// The metadata of the target of a link, [None] for other files and broken links.
+    target: Option<std::fs::Metadata>,
+    /// The target of a link, [None] for other files.
+    symlink: Option<SymLink>,
+}
+
+impl Local {
+    fn is_broken_link(&self) -> bool {
+        self.symlink.is_some() && self.target.is_none()
+    }
+}
+
 impl Meta {
     pub fn name(&self) -> Name {
-        Name::new(&self.path, self.file_type())
+        let broken = self.symlink().is_some_and(SymLink::is_broken);
+        let mut name = Name::new(&self.path, self.file_type()).with_broken_link(broken);
+        if let Some(alias) = self.alias {
+            name.name = alias.to_string();
+        }
+        name
+    }
+
+    /// Show the target of a symbolic link instead of the link itself, unless the link is broken.
+    pub fn dereference(&mut self) {
+        if let Some(local) = &mut self.local {
+            if let Some(target) = local.target.take() {
+                local.meta = target;
+                local.symlink = None;
+            }
+        }
+    }
+
+    /// Whether the content of `self` is listed under it: the directories, and the links to
+    /// directories unless the long layout shows the links themselves.
+    pub fn lists_content(&self, flags: &Flags) -> bool {
+        match self.file_type() {
+            FileType::Directory { .. } => true,
+            FileType::SymLink { is_dir: true } => flags.blocks.0.len() == 1,
+            _ => false,
+        }
+    }
+
+    pub fn symlink(&self) -> Option<&SymLink> {
+        self.local.as_ref()?.symlink.as_ref()
+    }
+
+    fn has(&self, key: Metakey) -> bool {
+        self.keys.contains(key) || self.keys.contains(Metakey::Complete)
     }
 
     pub fn size(&self) -> Option<Size> {
+        if let Some(bytes) = self.total_size {
+            return Some(Size::new(bytes));
+        }
+        // Local links have their own size, OpenDAL only knows about their target.
+        if let Some(local) = &self.local {
+            return Some(Size::from(&local.meta));
+        }
+        if !self.has(Metakey::ContentLength) {
+            return None;
+        }
         Some(Size::new(self.meta.content_length()))
     }
 
+    pub fn content_type(&self) -> Option<&str> {
+        if !self.has(Metakey::ContentType) {
+            return None;
+        }
+        self.meta.content_type()
+    }
+
+    pub fn content_md5(&self) -> Option<&str> {
+        if !self.has(Metakey::ContentMd5) {
+            return None;
+        }
+        self.meta.content_md5()
+    }
+
+    pub fn content_disposition(&self) -> Option<&str> {
+        if !self.has(Metakey::ContentDisposition) {
+            return None;
+        }
+        self.meta.content_disposition()
+    }
+
+    pub fn etag(&self) -> Option<&str> {
+        if !self.has(Metakey::Etag) {
+            return None;
+        }
+        self.meta.etag()
+    }
+
+    pub fn permissions(&self) -> Option<Permissions> {
+        self.local.as_ref().map(|l| Permissions::from(&l.meta))
+    }
+
+    #[cfg(unix)]
+    pub fn owner(&self) -> Option<Owner> {
+        self.local.as_ref().map(|l| Owner::from(&l.meta))
+    }
+
+    #[cfg(windows)]
+    pub fn owner(&self) -> Option<Owner> {
+        None
+    }
+
+    pub fn inode(&self) -> Option<INode> {
+        self.local.as_ref().map(|l| INode::from(&l.meta))
+    }
+
+    pub fn links(&self) -> Option<Links> {
+        self.local.as_ref().map(|l| Links::from(&l.meta))
+    }
+
     pub fn file_type(&self) -> FileType {
-        if self.meta.is_dir() {
-            return FileType::Directory;
+        if let Some(local) = &self.local {
+            #[cfg(unix)]
+            let file_type = FileType::new(
+                &local.meta,
+                local.target.as_ref(),
+                &Permissions::from(&local.meta),
+            );
+            #[cfg(windows)]
+            let file_type = FileType::new(&local.meta, local.target.as_ref(), &self.path);
+            return file_type;
         }
 
-        //TODO(kw): more types
-        FileType::File
+        if self.meta.is_dir() {
+            FileType::Directory { uid: false }
+        } else {
+            FileType::File {
+                uid: false,
+                exec: false,
+            }
+        }
     }
 
     pub fn modified_date(&self) -> Date {
+        if let Some(local) = &self.local {
+            if let Ok(modified) = local.meta.modified() {
+                return Date::from(modified);
+            }
+        }
+        if !self.has(Metakey::LastModified) {
+            return Date::Invalid;
+        }
         match self.meta.last_modified() {
             None => Date::Invalid,
             Some(offset) => Date::from(SystemTime::from(offset)),
@@ -186,27 +952,420 @@ impl Meta {
     }
 }
 
+#[cfg(test)]
+impl Meta {
+    /// Look up a file of the local filesystem, for the tests of the code rendering a `Meta`.
+    pub async fn from_local(path: &Path) -> io::Result<Self> {
+        let uri = Uri::parse(path, &crate::config_file::Config::with_none())?;
+        let dal = DAL::new(Path::new("/"), &uri, 1)?;
+        dal.from_path(&uri.path).await
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Meta;
+    use super::{s3_config, Meta, Uri, DAL};
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Flags;
+    use crate::meta::{Date, FileType};
+    use crate::ExitCode;
+    use assert_fs::prelude::*;
+    use clap::Parser;
+    use std::collections::HashMap;
     use std::fs::File;
+    use std::io::ErrorKind;
+    use std::path::Path;
+    use std::time::Instant;
     use tempfile::tempdir;
 
+    async fn list(dir: &Path, depth: usize, flags: &Flags) -> Meta {
+        let uri = Uri::parse(dir, &Config::with_none()).unwrap();
+        let dal = DAL::new(Path::new("/"), &uri, 4).unwrap();
+        let mut meta = dal.from_path(&uri.path).await.unwrap();
+        meta.sub_metas = dal.recurse_into(&meta, depth, flags).await.unwrap().0;
+        meta
+    }
+
+    #[tokio::test]
+    async fn test_recurse_into_depth() {
+        let cli = Cli::try_parse_from(["lsd", "--tree"]).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("one/two/three/file").touch().unwrap();
+
+        let meta = list(dir.path(), 2, &flags).await;
+        assert_eq!(1, meta.sub_metas.len());
+        let one = &meta.sub_metas[0];
+        assert_eq!("one", one.name().file_name());
+        assert_eq!(1, one.sub_metas.len());
+        let two = &one.sub_metas[0];
+        assert_eq!("two", two.name().file_name());
+        assert!(two.sub_metas.is_empty());
+
+        let meta = list(dir.path(), usize::MAX, &flags).await;
+        let three = &meta.sub_metas[0].sub_metas[0].sub_metas[0];
+        assert_eq!("file", three.sub_metas[0].name().file_name());
+    }
+
+    #[tokio::test]
     #[cfg(unix)]
-    #[test]
-    fn test_from_path_path() {
+    async fn test_local_metadata() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let flags = Flags::default();
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("file").touch().unwrap();
+        std::fs::set_permissions(
+            dir.child("file").path(),
+            std::fs::Permissions::from_mode(0o640),
+        )
+        .unwrap();
+
+        let meta = list(dir.path(), 1, &flags).await;
+        assert!(meta.permissions().is_some());
+        let file = &meta.sub_metas[0];
+        let permissions = file.permissions().unwrap();
+        assert!(permissions.user_read && permissions.user_write && !permissions.user_execute);
+        assert!(permissions.group_read && !permissions.other_read);
+        assert_eq!(Some(1), file.links().unwrap().count());
+        assert!(file.inode().unwrap().index().is_some());
+        assert!(file.owner().is_some());
+    }
+
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn test_recurse_into_unreadable() {
+        use std::os::unix::fs::PermissionsExt;
+
+        // The permissions do not stop root from listing the directory.
+        if users::get_current_uid() == 0 {
+            return;
+        }
+        let cli = Cli::try_parse_from(["lsd", "-R"]).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("locked/file").touch().unwrap();
+        dir.child("open/file").touch().unwrap();
+        let locked = dir.child("locked");
+        std::fs::set_permissions(locked.path(), std::fs::Permissions::from_mode(0o000)).unwrap();
+
+        let uri = Uri::parse(dir.path(), &Config::with_none()).unwrap();
+        let dal = DAL::new(Path::new("/"), &uri, 4).unwrap();
+        let meta = dal.from_path(&uri.path).await.unwrap();
+        let (sub_metas, code) = dal.recurse_into(&meta, 2, &flags).await.unwrap();
+        std::fs::set_permissions(locked.path(), std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        assert_eq!(ExitCode::MinorIssue, code);
+        let open = sub_metas
+            .iter()
+            .find(|m| m.name().file_name() == "open")
+            .unwrap();
+        assert_eq!(1, open.sub_metas.len());
+    }
+
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn test_symlinks() {
+        let flags = Flags::default();
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("dir/file").touch().unwrap();
+        dir.child("broken").symlink_to_file("missing").unwrap();
+        dir.child("link").symlink_to_dir("dir").unwrap();
+
+        let meta = list(dir.path(), 1, &flags).await;
+        let names: Vec<_> = meta
+            .sub_metas
+            .iter()
+            .map(|m| (m.name().file_name().to_string(), m.file_type()))
+            .collect();
+        assert!(names.contains(&("broken".to_string(), FileType::SymLink { is_dir: false })));
+        assert!(names.contains(&("link".to_string(), FileType::SymLink { is_dir: true })));
+
+        let broken = meta
+            .sub_metas
+            .iter()
+            .find(|m| m.name().file_name() == "broken")
+            .unwrap();
+        assert!(broken.symlink().unwrap().is_broken());
+        assert_eq!(
+            Some("missing".to_string()),
+            broken.symlink().unwrap().symlink_string()
+        );
+    }
+
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn test_dereference() {
+        let cli = Cli::try_parse_from(["lsd", "-L"]).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("dir").create_dir_all().unwrap();
+        dir.child("broken").symlink_to_file("missing").unwrap();
+        dir.child("link").symlink_to_dir("dir").unwrap();
+
+        let meta = list(dir.path(), 1, &flags).await;
+        for entry in &meta.sub_metas {
+            match entry.name().file_name() {
+                "link" => {
+                    assert_eq!(FileType::Directory { uid: false }, entry.file_type());
+                    assert!(entry.symlink().is_none());
+                }
+                "broken" => assert!(entry.symlink().is_some()),
+                _ => {}
+            }
+        }
+    }
+
+    fn names(meta: &Meta) -> Vec<String> {
+        let mut names: Vec<_> = meta
+            .sub_metas
+            .iter()
+            .map(|m| m.name().file_name().to_string())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[tokio::test]
+    async fn test_recurse_into_hidden() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child(".hidden").touch().unwrap();
+        dir.child("visible").touch().unwrap();
+
+        let meta = list(dir.path(), 1, &Flags::default()).await;
+        assert_eq!(vec!["visible"], names(&meta));
+
+        let cli = Cli::try_parse_from(["lsd", "-A"]).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+        let meta = list(dir.path(), 1, &flags).await;
+        assert_eq!(vec![".hidden", "visible"], names(&meta));
+
+        let cli = Cli::try_parse_from(["lsd", "-a"]).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+        let meta = list(dir.path(), 1, &flags).await;
+        assert_eq!(vec![".", "..", ".hidden", "visible"], names(&meta));
+
+        let cli = Cli::try_parse_from(["lsd", "-a", "--tree"]).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+        let meta = list(dir.path(), 1, &flags).await;
+        assert_eq!(vec![".hidden", "visible"], names(&meta));
+    }
+
+    #[tokio::test]
+    async fn test_recurse_into_hide_dot_keys() {
+        // The memory service is shared by the tests, each of them has its own directory.
+        let uri = Uri::parse(Path::new("memory:///hide-dot-keys/"), &Config::with_none()).unwrap();
+        let dal = DAL::new(Path::new("/"), &uri, 4).unwrap();
+        dal.write(Path::new("hide-dot-keys/.cache/key"), vec![], None)
+            .await
+            .unwrap();
+        dal.write(Path::new("hide-dot-keys/key"), vec![], None)
+            .await
+            .unwrap();
+        let meta = dal.from_path(&uri.path).await.unwrap();
+
+        let mut flags = Flags::default();
+        let sub_metas = dal.recurse_into(&meta, 1, &flags).await.unwrap().0;
+        assert_eq!(2, sub_metas.len());
+
+        flags.hide_dot_keys.0 = true;
+        let sub_metas = dal.recurse_into(&meta, 1, &flags).await.unwrap().0;
+        assert_eq!(1, sub_metas.len());
+        assert_eq!("key", sub_metas[0].name().file_name());
+    }
+
+    #[tokio::test]
+    async fn test_recurse_into_ignore_globs() {
+        let cli = Cli::try_parse_from(["lsd", "--tree", "-I", "skip"]).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("one/skip").touch().unwrap();
+        dir.child("one/keep").touch().unwrap();
+        dir.child("skip/file").touch().unwrap();
+
+        let meta = list(dir.path(), usize::MAX, &flags).await;
+        assert_eq!(1, meta.sub_metas.len());
+        let one = &meta.sub_metas[0];
+        assert_eq!(1, one.sub_metas.len());
+        assert_eq!("keep", one.sub_metas[0].name().file_name());
+    }
+
+    #[tokio::test]
+    async fn test_recurse_into_filters() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("small").write_str("0123").unwrap();
+        dir.child("dir/large").write_binary(&[0; 2048]).unwrap();
+
+        let names = |meta: &Meta| {
+            let mut names: Vec<_> = meta
+                .sub_metas
+                .iter()
+                .map(|sub| sub.name().file_name().to_string())
+                .collect();
+            names.sort();
+            names
+        };
+
+        let cli = Cli::try_parse_from(["lsd", "--tree", "--larger", "1KB"]).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+        let meta = list(dir.path(), usize::MAX, &flags).await;
+        assert_eq!(vec!["dir"], names(&meta));
+        assert_eq!(vec!["large"], names(&meta.sub_metas[0]));
+
+        let cli = Cli::try_parse_from(["lsd", "--smaller", "1K", "--newer", "1d"]).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+        let meta = list(dir.path(), 1, &flags).await;
+        assert_eq!(vec!["dir", "small"], names(&meta));
+
+        let cli = Cli::try_parse_from(["lsd", "--older", "1d"]).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+        let meta = list(dir.path(), 1, &flags).await;
+        assert_eq!(vec!["dir"], names(&meta));
+    }
+
+    #[tokio::test]
+    async fn test_walk() {
+        let cli = Cli::try_parse_from(["lsd", "-R", "--jobs", "1"]).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("one/two/file").touch().unwrap();
+        dir.child("file").touch().unwrap();
+
+        let uri = Uri::parse(dir.path(), &Config::with_none()).unwrap();
+        let dal = DAL::new(Path::new("/"), &uri, flags.concurrency.0).unwrap();
+        let meta = dal.from_path(&uri.path).await.unwrap();
+        for (depth, expected) in [(1, 2), (2, 3), (usize::MAX, 4)] {
+            let mut names = vec![];
+            dal.walk(&meta, depth, &flags, |_, entry| {
+                names.push(entry.name().file_name().to_string())
+            })
+            .await
+            .unwrap();
+            assert_eq!(expected, names.len());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_total_size() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("one/a").write_str("0123456789").unwrap();
+        dir.child("one/two/b")
+            .write_str("01234567890123456789")
+            .unwrap();
+        dir.child("one/.hidden").write_str("01234").unwrap();
+        let one = std::fs::metadata(dir.child("one").path()).unwrap().len();
+        let two = std::fs::metadata(dir.child("one/two").path())
+            .unwrap()
+            .len();
+
+        let uri = Uri::parse(dir.path(), &Config::with_none()).unwrap();
+        let dal = DAL::new(Path::new("/"), &uri, 4).unwrap();
+        // What the listing hides or filters out is still counted.
+        for (argv, depth, expected) in [
+            (vec!["lsd", "-l", "--total-size"], 1, one + two + 35),
+            (
+                vec!["lsd", "-l", "--tree", "--total-size"],
+                usize::MAX,
+                one + two + 35,
+            ),
+            (
+                vec!["lsd", "-l", "--tree", "--total-size", "-I", "two"],
+                usize::MAX,
+                one + two + 35,
+            ),
+            (
+                vec!["lsd", "-l", "-R", "--total-size", "--larger", "15"],
+                usize::MAX,
+                one + two + 35,
+            ),
+            (
+                vec!["lsd", "-l", "--total-size", "--total-size-depth", "1"],
+                1,
+                one + two + 15,
+            ),
+            (
+                vec![
+                    "lsd",
+                    "-l",
+                    "--tree",
+                    "--total-size",
+                    "--total-size-depth",
+                    "1",
+                ],
+                usize::MAX,
+                one + two + 15,
+            ),
+        ] {
+            let cli = Cli::try_parse_from(argv).unwrap();
+            let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+            let mut meta = dal.from_path(&uri.path).await.unwrap();
+            meta.sub_metas = dal.recurse_into(&meta, depth, &flags).await.unwrap().0;
+            dal.total_size(&mut meta, depth, &flags, None)
+                .await
+                .unwrap();
+
+            let one_meta = &meta.sub_metas[0];
+            assert_eq!(Some(expected), one_meta.size().map(|s| s.get_bytes()));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_total_size_timeout() {
+        let cli = Cli::try_parse_from(["lsd", "-l", "--total-size"]).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("one/a").touch().unwrap();
+
+        let uri = Uri::parse(dir.path(), &Config::with_none()).unwrap();
+        let dal = DAL::new(Path::new("/"), &uri, 4).unwrap();
+        let mut meta = dal.from_path(&uri.path).await.unwrap();
+        let err = dal
+            .total_size(&mut meta, 0, &flags, Some(Instant::now()))
+            .await
+            .unwrap_err();
+        assert_eq!(ErrorKind::TimedOut, err.kind());
+    }
+
+    #[tokio::test]
+    async fn test_recurse_into_tree_directory_only() {
+        let cli = Cli::try_parse_from(["lsd", "--tree", "-d"]).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("one/two/file").touch().unwrap();
+        dir.child("file").touch().unwrap();
+
+        let meta = list(dir.path(), usize::MAX, &flags).await;
+        assert_eq!(1, meta.sub_metas.len());
+        let one = &meta.sub_metas[0];
+        assert_eq!(1, one.sub_metas.len());
+        assert!(one.sub_metas[0].sub_metas.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_from_path_path() {
         let dir = assert_fs::TempDir::new().unwrap();
-        let meta = Meta::from_path(dir.path(), false).unwrap();
+        let uri = Uri::parse(dir.path(), &Config::with_none()).unwrap();
+        let dal = DAL::new(Path::new("/"), &uri, 4).unwrap();
+        let meta = dal.from_path(&uri.path).await.unwrap();
         assert_eq!(meta.path, dir.path())
     }
 
-    #[test]
-    fn test_from_path() {
+    #[tokio::test]
+    async fn test_from_path() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
+        let uri = Uri::parse(tmp_dir.path(), &Config::with_none()).unwrap();
+        let dal = DAL::new(Path::new("/"), &uri, 4).unwrap();
 
         let path_a = tmp_dir.path().join("aaa.aa");
         File::create(&path_a).expect("failed to create file");
-        let meta_a = Meta::from_path(&path_a, false).expect("failed to get meta");
+        let meta_a = dal.from_path(&path_a).await.expect("failed to get meta");
 
         let path_b = tmp_dir.path().join("bbb.bb");
         let path_c = tmp_dir.path().join("ccc.cc");
@@ -221,26 +1380,67 @@ mod tests {
         std::os::windows::fs::symlink_file(&path_c, &path_b)
             .expect("failed to create broken symlink");
 
-        let meta_b = Meta::from_path(&path_b, true).expect("failed to get meta");
+        let mut meta_b = dal.from_path(&path_b).await.expect("failed to get meta");
+        meta_b.dereference();
 
         assert!(
-            meta_a.inode.is_some()
-                && meta_a.links.is_some()
-                && meta_a.size.is_some()
-                && meta_a.date.is_some()
-                && meta_a.owner.is_some()
-                && meta_a.permissions.is_some()
-                && meta_a.access_control.is_some()
+            meta_a.inode().is_some()
+                && meta_a.links().is_some()
+                && meta_a.size().is_some()
+                && meta_a.modified_date() != Date::Invalid
+                && meta_a.owner().is_some()
+                && meta_a.permissions().is_some()
         );
 
-        assert!(
-            meta_b.inode.is_none()
-                && meta_b.links.is_none()
-                && meta_b.size.is_none()
-                && meta_b.date.is_none()
-                && meta_b.owner.is_none()
-                && meta_b.permissions.is_none()
-                && meta_b.access_control.is_none()
-        );
+        // A broken link is kept as it is, OpenDAL knows nothing about it.
+        assert!(meta_b.symlink().is_some_and(|link| link.is_broken()));
+        assert_eq!(FileType::SymLink { is_dir: false }, meta_b.file_type());
+        assert!(meta_b.content_type().is_none() && meta_b.etag().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_memory_seeded_metadata() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let fixture = dir.child("fixture.yaml");
+        fixture
+            .write_str(
+                "- path: seeded/new\n  size: 42\n  modified: 1h\n  content-type: text/plain\n\
+                 - path: seeded/old\n  content: old\n  modified: 2020-01-01\n",
+            )
+            .unwrap();
+        super::seed(fixture.path()).await.unwrap();
+
+        let cli = Cli::try_parse_from(["lsd", "-l"]).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+        let meta = list(Path::new("memory:///seeded/"), 1, &flags).await;
+        let new = &meta.sub_metas[0];
+        assert_eq!("new", new.name().file_name());
+        assert_eq!(Some(42), new.size().map(|s| s.get_bytes()));
+        assert_ne!(Date::Invalid, new.modified_date());
+        // What has been seeded is known, even when it has not been asked for.
+        assert_eq!(Some("text/plain"), new.content_type());
+        assert_eq!(Some(3), meta.sub_metas[1].size().map(|s| s.get_bytes()));
+    }
+
+    #[test]
+    fn test_s3_config() {
+        let mut config = HashMap::from([("endpoint".into(), "http://localhost:9000".into())]);
+        s3_config(&mut config);
+        assert_eq!(None, config.get("enable_virtual_host_style"));
+
+        let mut config = HashMap::from([("path_style".into(), "false".into())]);
+        s3_config(&mut config);
+        assert_eq!("true", config["enable_virtual_host_style"]);
+        assert_eq!(None, config.get("path_style"));
+
+        let mut config = HashMap::from([
+            ("path_style".into(), "true".into()),
+            ("anonymous".into(), "true".into()),
+            ("access_key_id".into(), "AK".into()),
+        ]);
+        s3_config(&mut config);
+        assert_eq!(None, config.get("enable_virtual_host_style"));
+        assert_eq!(None, config.get("access_key_id"));
+        assert_eq!("true", config["disable_config_load"]);
     }
 }
@@ -0,0 +1,354 @@
+//! This module defines the [AutoindexLayer], which lists the directories of the `http` service
+//! from the index pages web servers generate for them, like the `autoindex` of nginx or the
+//! `mod_autoindex` of Apache. The service itself can only read and stat files.
+
+use async_trait::async_trait;
+use opendal::ops::{OpList, OpRead, OpScan, OpWrite};
+use opendal::raw::oio::{self, Entry};
+use opendal::raw::{
+    format_authorization_by_basic, format_authorization_by_bearer, normalize_root,
+    percent_encode_path, Accessor, AccessorCapability, AccessorInfo, Layer, LayeredAccessor,
+    RpList, RpRead, RpScan, RpWrite,
+};
+use opendal::{EntryMode, Error, ErrorKind, Metadata, Result};
+use percent_encoding::percent_decode_str;
+use reqwest::{header, StatusCode};
+
+use std::collections::{HashMap, HashSet};
+use std::mem;
+
+/// Add the listing of directories to the `http` service, built from the same configuration.
+///
+/// Only the names and the kinds of the entries are listed, their other metadata is read by
+/// a `HEAD` request when needed.
+#[derive(Clone, Debug)]
+pub(super) struct AutoindexLayer {
+    client: reqwest::Client,
+    endpoint: String,
+    root: String,
+    authorization: Option<String>,
+}
+
+impl AutoindexLayer {
+    pub(super) fn new(config: &HashMap<String, String>) -> Result<Self> {
+        let authorization = match (config.get("username"), config.get("token")) {
+            (_, Some(token)) => Some(format_authorization_by_bearer(token)?),
+            (Some(username), None) => Some(format_authorization_by_basic(
+                username,
+                config.get("password").map_or("", String::as_str),
+            )?),
+            (None, None) => None,
+        };
+        Ok(Self {
+            client: reqwest::Client::new(),
+            endpoint: config.get("endpoint").cloned().unwrap_or_default(),
+            root: normalize_root(config.get("root").map_or("/", String::as_str)),
+            authorization,
+        })
+    }
+}
+
+impl<A: Accessor> Layer<A> for AutoindexLayer {
+    type LayeredAccessor = AutoindexAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        AutoindexAccessor {
+            inner,
+            index: self.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct AutoindexAccessor<A: Accessor> {
+    inner: A,
+    index: AutoindexLayer,
+}
+
+impl<A: Accessor> AutoindexAccessor<A> {
+    /// Fetch the index page of the directory at `path`, relative to the root.
+    async fn index_page(&self, path: &str) -> Result<String> {
+        let url = format!(
+            "{}{}",
+            self.index.endpoint,
+            percent_encode_path(&format!("{}{path}", self.index.root))
+        );
+        let mut req = self.index.client.get(&url);
+        if let Some(authorization) = &self.index.authorization {
+            req = req.header(header::AUTHORIZATION, authorization);
+        }
+
+        let resp = req.send().await.map_err(|err| request_error(&url, err))?;
+        let kind = match resp.status() {
+            status if status.is_success() => {
+                return resp.text().await.map_err(|err| request_error(&url, err))
+            }
+            StatusCode::NOT_FOUND => ErrorKind::NotFound,
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => ErrorKind::PermissionDenied,
+            _ => ErrorKind::Unexpected,
+        };
+        Err(Error::new(kind, "the index page could not be fetched")
+            .with_context("url", url)
+            .with_context("status", resp.status().as_str()))
+    }
+}
+
+fn request_error(url: &str, err: reqwest::Error) -> Error {
+    Error::new(ErrorKind::Unexpected, "sending the index request failed")
+        .with_context("url", url)
+        .set_source(err)
+        .set_temporary()
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for AutoindexAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Pager = AutoindexPager;
+    type BlockingPager = ();
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    fn metadata(&self) -> AccessorInfo {
+        let mut info = self.inner.info();
+        info.set_capabilities(info.capabilities() | AccessorCapability::List);
+        info
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.inner.write(path, args).await
+    }
+
+    async fn list(&self, path: &str, _: OpList) -> Result<(RpList, Self::Pager)> {
+        let dir = if path == "/" { "" } else { path };
+        let page = self.index_page(dir).await?;
+        let absolute = format!("{}{dir}", self.index.root);
+        let entries = parse_index(&page, &absolute)
+            .into_iter()
+            .map(|name| {
+                let mode = if name.ends_with('/') {
+                    EntryMode::DIR
+                } else {
+                    EntryMode::FILE
+                };
+                Entry::with(format!("{dir}{name}"), Metadata::new(mode))
+            })
+            .collect();
+        Ok((RpList::default(), AutoindexPager(entries)))
+    }
+
+    async fn scan(&self, _: &str, _: OpScan) -> Result<(RpScan, Self::Pager)> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "index pages can only be listed one directory at a time",
+        ))
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.inner.blocking_write(path, args)
+    }
+
+    fn blocking_list(&self, _: &str, _: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "index pages can not be listed in blocking mode",
+        ))
+    }
+
+    fn blocking_scan(&self, _: &str, _: OpScan) -> Result<(RpScan, Self::BlockingPager)> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "index pages can not be listed in blocking mode",
+        ))
+    }
+}
+
+/// The entries of a listed index page, returned as a single page.
+pub(super) struct AutoindexPager(Vec<Entry>);
+
+#[async_trait]
+impl oio::Page for AutoindexPager {
+    async fn next(&mut self) -> Result<Option<Vec<Entry>>> {
+        if self.0.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(mem::take(&mut self.0)))
+    }
+}
+
+/// The names of the entries linked from the index page of the directory at `absolute`, with
+/// a trailing `/` for the directories.
+///
+/// Only the links to the direct children of the directory are kept: the links to the parent,
+/// to other sites, and the `?C=N;O=D` sorting links of Apache are not entries.
+fn parse_index(page: &str, absolute: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    let mut names = vec![];
+    let lowercase = page.to_ascii_lowercase();
+    for (start, _) in lowercase.match_indices("href=") {
+        let rest = &page[start + "href=".len()..];
+        let href = match rest.chars().next() {
+            Some(quote @ ('"' | '\'')) => rest[1..].split(quote).next(),
+            _ => rest.split(|c: char| c.is_whitespace() || c == '>').next(),
+        };
+        let href = match href {
+            Some(href) => unescape(href),
+            None => continue,
+        };
+        // nginx writes the names holding a `:` as `./name`, not to be taken for a scheme.
+        if href.contains(['?', '#']) || (href.contains(':') && !href.starts_with(['.', '/'])) {
+            continue;
+        }
+        let href = percent_decode_str(&href).decode_utf8_lossy();
+        let name = match href.strip_prefix('/') {
+            Some(_) => match href.strip_prefix(absolute) {
+                Some(name) => name,
+                None => continue,
+            },
+            None => href.strip_prefix("./").unwrap_or(&href),
+        };
+
+        let base = name.strip_suffix('/').unwrap_or(name);
+        if base.is_empty() || base == "." || base == ".." || base.contains('/') {
+            continue;
+        }
+        if seen.insert(name.to_string()) {
+            names.push(name.to_string());
+        }
+    }
+    names
+}
+
+/// Replace the character references HTML allows in attributes by the characters they stand for.
+fn unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_index;
+
+    use crate::config_file::Config;
+    use crate::dal::{Uri, DAL};
+    use crate::flags::{Block, Blocks, Flags};
+
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::path::Path;
+    use std::thread;
+
+    /// Serve the `pages` by path on a local port, one request per connection, and return the
+    /// address of the server.
+    fn serve(pages: &'static [(&'static str, &'static str)]) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut request = String::new();
+                BufReader::new(&stream).read_line(&mut request).unwrap();
+                let mut parts = request.split_whitespace();
+                let (method, path) = (parts.next().unwrap(), parts.next().unwrap());
+                let response = match pages.iter().find(|(p, _)| *p == path) {
+                    Some((_, body)) => format!(
+                        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        if method == "HEAD" { "" } else { body }
+                    ),
+                    None => {
+                        "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                            .to_string()
+                    }
+                };
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+        addr
+    }
+
+    #[test]
+    fn test_parse_nginx_index() {
+        let page = r#"<html>
+<head><title>Index of /releases/</title></head>
+<body>
+<h1>Index of /releases/</h1><hr><pre><a href="../">../</a>
+<a href="v1.0/">v1.0/</a>                                              18-Oct-2026 10:00       -
+<a href="dilu%201.0.tar.gz">dilu 1.0.tar.gz</a>                        18-Oct-2026 10:00    2048
+<a href="notes&amp;todo.txt">notes&amp;todo.txt</a>                   18-Oct-2026 10:00      12
+<a href="./12:00.log">12:00.log</a>                                   18-Oct-2026 12:00     512
+</pre><hr></body>
+</html>"#;
+        assert_eq!(
+            vec!["v1.0/", "dilu 1.0.tar.gz", "notes&todo.txt", "12:00.log"],
+            parse_index(page, "/releases/")
+        );
+    }
+
+    #[test]
+    fn test_parse_apache_index() {
+        let page = r#"<table>
+<tr><th><a href="?C=N;O=D">Name</a></th><th><a href="?C=M;O=A">Last modified</a></th></tr>
+<tr><td><a href="/pub/">Parent Directory</a></td></tr>
+<tr><td><a href="/pub/releases/app.zip">app.zip</a></td></tr>
+<tr><td><a href='nightly/'>nightly/</a></td></tr>
+<tr><td><a href="nightly/">nightly/</a></td></tr>
+<tr><td><a href="https://httpd.apache.org/">Apache</a></td></tr>
+<tr><td><a href="nightly/latest.zip">latest.zip</a></td></tr>
+</table>"#;
+        assert_eq!(
+            vec!["app.zip", "nightly/"],
+            parse_index(page, "/pub/releases/")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_http() {
+        let addr = serve(&[
+            (
+                "/releases/",
+                r#"<a href="../">../</a><a href="v1/">v1/</a><a href="app.zip">app.zip</a>"#,
+            ),
+            ("/releases/v1/", r#"<a href="../">../</a>"#),
+            ("/releases/app.zip", "PK"),
+        ]);
+        let input = format!("http://{addr}/releases/");
+        let uri = Uri::parse(Path::new(&input), &Config::with_none()).unwrap();
+        let dal = DAL::new(Path::new("/"), &uri, 1).unwrap();
+
+        let meta = dal.from_path(&uri.path).await.unwrap();
+        let flags = Flags {
+            blocks: Blocks(vec![Block::Size, Block::Name]),
+            ..Flags::default()
+        };
+        let (mut metas, _) = dal.recurse_into(&meta, 1, &flags).await.unwrap();
+        metas.sort_by_key(|meta| meta.path.clone());
+        let names: Vec<_> = metas
+            .iter()
+            .map(|m| m.name().file_name().to_string())
+            .collect();
+        assert_eq!(vec!["app.zip", "v1"], names);
+        assert_eq!(Some(2), metas[0].size().map(|size| size.get_bytes()));
+        assert!(metas[1].file_type().is_dirlike());
+        assert_eq!(
+            b"PK".to_vec(),
+            dal.read(Path::new("releases/app.zip")).await.unwrap()
+        );
+    }
+}
@@ -0,0 +1,80 @@
+//! This module defines the [ContentTypeLayer], which sets the content type of the files written
+//! through an appending writer, as OpenDAL only takes it with the whole content at once.
+
+use async_trait::async_trait;
+use opendal::ops::{OpList, OpRead, OpScan, OpWrite};
+use opendal::raw::{Accessor, Layer, LayeredAccessor, RpList, RpRead, RpScan, RpWrite};
+use opendal::Result;
+
+/// Write every file with the given content type.
+///
+/// The content type is passed in the arguments of each write, for the services to set it
+/// when they create the file. The multipart uploads of S3 in OpenDAL 0.30 still ignore it.
+#[derive(Clone, Debug)]
+pub(super) struct ContentTypeLayer(pub(super) String);
+
+impl<A: Accessor> Layer<A> for ContentTypeLayer {
+    type LayeredAccessor = ContentTypeAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        ContentTypeAccessor {
+            inner,
+            content_type: self.0.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct ContentTypeAccessor<A: Accessor> {
+    inner: A,
+    content_type: String,
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for ContentTypeAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Pager = A::Pager;
+    type BlockingPager = A::BlockingPager;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let args = args.with_content_type(&self.content_type);
+        self.inner.write(path, args).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.inner.list(path, args).await
+    }
+
+    async fn scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::Pager)> {
+        self.inner.scan(path, args).await
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        let args = args.with_content_type(&self.content_type);
+        self.inner.blocking_write(path, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.inner.blocking_list(path, args)
+    }
+
+    fn blocking_scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::BlockingPager)> {
+        self.inner.blocking_scan(path, args)
+    }
+}
@@ -0,0 +1,544 @@
+//! This module defines the key-value stores browsed as a tree: Redis, sled and RocksDB.
+//!
+//! Their keys are split into directories at a delimiter, `:` for Redis and `/` for the others
+//! unless the `delimiter` of the service configuration says otherwise, so that the Redis key
+//! `user:42:name` is listed as the file `name` of the directory `user/42/`. The size of a file
+//! is the length of its value.
+
+use opendal::raw::adapters::kv;
+
+use std::collections::HashMap;
+use std::io::{self, Error, ErrorKind};
+
+#[cfg(any(feature = "sled", feature = "rocksdb"))]
+use opendal::Scheme;
+#[cfg(any(feature = "sled", feature = "rocksdb"))]
+use std::path::{Path, PathBuf};
+
+/// How the paths of the tree are turned into keys, and back.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) struct Delimiter(char);
+
+impl Delimiter {
+    /// The `delimiter` of the service configuration, or else the `usual` one of the store.
+    pub(super) fn new(config: &HashMap<String, String>, usual: char) -> io::Result<Self> {
+        let delimiter = match config.get("delimiter") {
+            Some(delimiter) => delimiter,
+            None => return Ok(Self(usual)),
+        };
+        let mut chars = delimiter.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(Self(c)),
+            _ => Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("invalid delimiter {delimiter:?}, it should be a single character"),
+            )),
+        }
+    }
+
+    /// The key stored at `path`.
+    pub(super) fn key(self, path: &str) -> String {
+        path.replace('/', self.0.encode_utf8(&mut [0; 4]))
+    }
+
+    /// The path the `key` is listed at.
+    pub(super) fn path(self, key: &str) -> String {
+        key.replace(self.0, "/")
+    }
+}
+
+/// Split the path of a `sled://` or `rocksdb://` input into the directory of the store and the
+/// path inside of it.
+///
+/// The store is the nearest directory holding the `marker` file every store of the `scheme`
+/// writes, `conf` for sled and `CURRENT` for RocksDB, so that `sled:///var/db/app/user/` lists
+/// `user/` in the store at `/var/db/app`. Nothing is created when there is no store.
+#[cfg(any(feature = "sled", feature = "rocksdb"))]
+pub(super) fn split_datadir(
+    scheme: Scheme,
+    path: &Path,
+    marker: &str,
+) -> io::Result<(PathBuf, String)> {
+    let datadir = path
+        .ancestors()
+        .find(|dir| dir.join(marker).is_file())
+        .ok_or_else(|| {
+            Error::new(
+                ErrorKind::NotFound,
+                format!("{}: no {scheme} store found", path.display()),
+            )
+        })?;
+
+    let mut inner = path
+        .strip_prefix(datadir)
+        .map(|p| p.to_string_lossy().replace('\\', "/"))
+        .unwrap_or_default();
+    // The components of a path lose its trailing `/`, which tells the directories apart.
+    if !inner.is_empty() && path.to_string_lossy().ends_with('/') {
+        inner.push('/');
+    }
+    let datadir = if datadir.as_os_str().is_empty() {
+        PathBuf::from(".")
+    } else {
+        datadir.to_path_buf()
+    };
+    Ok((datadir, inner))
+}
+
+/// The value of `key` in the Redis `SCAN` patterns, where `*?[]\` are special.
+#[cfg(feature = "redis")]
+fn escape_glob(key: &str) -> String {
+    let mut escaped = String::with_capacity(key.len());
+    for c in key.chars() {
+        if matches!(c, '*' | '?' | '[' | ']' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(feature = "redis")]
+pub(super) use self::redis_store::Redis;
+
+#[cfg(feature = "redis")]
+mod redis_store {
+    use super::{escape_glob, kv, Delimiter};
+
+    use async_trait::async_trait;
+    use opendal::raw::AccessorCapability;
+    use opendal::{Error, ErrorKind, Result, Scheme};
+    use redis::aio::ConnectionManager;
+    use redis::{
+        AsyncCommands, Client, ConnectionAddr, ConnectionInfo, RedisConnectionInfo, RedisError,
+    };
+    use tokio::sync::OnceCell;
+
+    use std::collections::HashMap;
+    use std::fmt::{self, Debug, Formatter};
+
+    const DEFAULT_PORT: u16 = 6379;
+    /// The number of keys asked for by each `SCAN` round trip.
+    const SCAN_COUNT: usize = 1000;
+
+    pub(in crate::dal) struct Redis {
+        client: Client,
+        conn: OnceCell<ConnectionManager>,
+        delimiter: Delimiter,
+    }
+
+    // Written by hand, so that the password is never printed.
+    impl Debug for Redis {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            let info = self.client.get_connection_info();
+            f.debug_struct("Redis")
+                .field("addr", &info.addr)
+                .field("db", &info.redis.db)
+                .field("delimiter", &self.delimiter)
+                .finish()
+        }
+    }
+
+    impl Redis {
+        /// Connect, once used, to the `endpoint` of the configuration, like `tcp://host:6379`,
+        /// with the `db`, `username` and `password` which are set.
+        pub(in crate::dal) fn new(config: &HashMap<String, String>) -> Result<Self> {
+            let endpoint = config
+                .get("endpoint")
+                .map_or("tcp://127.0.0.1", String::as_str);
+            let url = url::Url::parse(endpoint).map_err(|err| {
+                Error::new(ErrorKind::ConfigInvalid, "endpoint is invalid")
+                    .with_context("endpoint", endpoint)
+                    .set_source(err)
+            })?;
+            let host = url.host_str().unwrap_or("127.0.0.1").to_string();
+            let db = match config.get("db") {
+                Some(db) => db.parse().map_err(|err| {
+                    Error::new(ErrorKind::ConfigInvalid, "db is not a number")
+                        .with_context("db", db)
+                        .set_source(err)
+                })?,
+                None => 0,
+            };
+            let info = ConnectionInfo {
+                addr: ConnectionAddr::Tcp(host, url.port().unwrap_or(DEFAULT_PORT)),
+                redis: RedisConnectionInfo {
+                    db,
+                    username: config.get("username").cloned(),
+                    password: config.get("password").cloned(),
+                },
+            };
+
+            Ok(Self {
+                client: Client::open(info).map_err(redis_error)?,
+                conn: OnceCell::new(),
+                delimiter: Delimiter::new(config, ':')
+                    .map_err(|err| Error::new(ErrorKind::ConfigInvalid, &err.to_string()))?,
+            })
+        }
+
+        async fn conn(&self) -> Result<ConnectionManager> {
+            self.conn
+                .get_or_try_init(|| ConnectionManager::new(self.client.clone()))
+                .await
+                .cloned()
+                .map_err(redis_error)
+        }
+    }
+
+    #[async_trait]
+    impl kv::Adapter for Redis {
+        fn metadata(&self) -> kv::Metadata {
+            kv::Metadata::new(
+                Scheme::Redis,
+                &self.client.get_connection_info().addr.to_string(),
+                AccessorCapability::Read | AccessorCapability::Write | AccessorCapability::Scan,
+            )
+        }
+
+        async fn get(&self, path: &str) -> Result<Option<Vec<u8>>> {
+            let mut conn = self.conn().await?;
+            conn.get(self.delimiter.key(path))
+                .await
+                .map_err(redis_error)
+        }
+
+        async fn set(&self, path: &str, value: &[u8]) -> Result<()> {
+            let mut conn = self.conn().await?;
+            conn.set(self.delimiter.key(path), value)
+                .await
+                .map_err(redis_error)
+        }
+
+        async fn delete(&self, path: &str) -> Result<()> {
+            let mut conn = self.conn().await?;
+            conn.del(self.delimiter.key(path))
+                .await
+                .map_err(redis_error)
+        }
+
+        /// `SCAN` the keys starting with the key of `path`, which does not block the server
+        /// like `KEYS` would.
+        async fn scan(&self, path: &str) -> Result<Vec<String>> {
+            let mut conn = self.conn().await?;
+            let pattern = format!("{}*", escape_glob(&self.delimiter.key(path)));
+            let mut cursor = 0_u64;
+            let mut paths = vec![];
+            loop {
+                let (next, keys): (u64, Vec<Vec<u8>>) = redis::cmd("SCAN")
+                    .arg(cursor)
+                    .arg("MATCH")
+                    .arg(&pattern)
+                    .arg("COUNT")
+                    .arg(SCAN_COUNT)
+                    .query_async(&mut conn)
+                    .await
+                    .map_err(redis_error)?;
+                paths.extend(
+                    keys.iter()
+                        .map(|key| self.delimiter.path(&String::from_utf8_lossy(key))),
+                );
+                if next == 0 {
+                    return Ok(paths);
+                }
+                cursor = next;
+            }
+        }
+    }
+
+    fn redis_error(err: RedisError) -> Error {
+        Error::new(ErrorKind::Unexpected, err.category()).set_source(err)
+    }
+}
+
+#[cfg(feature = "sled")]
+pub(super) use self::sled_store::Sled;
+
+#[cfg(feature = "sled")]
+mod sled_store {
+    use super::{kv, Delimiter};
+
+    use async_trait::async_trait;
+    use opendal::raw::AccessorCapability;
+    use opendal::{Error, ErrorKind, Result, Scheme};
+
+    use std::collections::HashMap;
+    use std::fmt::{self, Debug, Formatter};
+
+    pub(in crate::dal) struct Sled {
+        datadir: String,
+        db: sled::Db,
+        delimiter: Delimiter,
+    }
+
+    impl Debug for Sled {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            f.debug_struct("Sled")
+                .field("datadir", &self.datadir)
+                .field("delimiter", &self.delimiter)
+                .finish()
+        }
+    }
+
+    impl Sled {
+        /// Open the store at the `datadir` of the configuration.
+        pub(in crate::dal) fn new(config: &HashMap<String, String>) -> Result<Self> {
+            let datadir = config.get("datadir").cloned().ok_or_else(|| {
+                Error::new(ErrorKind::ConfigInvalid, "datadir is required but not set")
+            })?;
+            let db = sled::open(&datadir).map_err(|err| {
+                Error::new(ErrorKind::ConfigInvalid, "the store could not be opened")
+                    .with_context("datadir", datadir.clone())
+                    .set_source(err)
+            })?;
+            Ok(Self {
+                datadir,
+                db,
+                delimiter: Delimiter::new(config, '/')
+                    .map_err(|err| Error::new(ErrorKind::ConfigInvalid, &err.to_string()))?,
+            })
+        }
+    }
+
+    #[async_trait]
+    impl kv::Adapter for Sled {
+        fn metadata(&self) -> kv::Metadata {
+            kv::Metadata::new(
+                Scheme::Sled,
+                &self.datadir,
+                AccessorCapability::Read | AccessorCapability::Write | AccessorCapability::Scan,
+            )
+        }
+
+        async fn get(&self, path: &str) -> Result<Option<Vec<u8>>> {
+            let value = self.db.get(self.delimiter.key(path)).map_err(sled_error)?;
+            Ok(value.map(|value| value.to_vec()))
+        }
+
+        async fn set(&self, path: &str, value: &[u8]) -> Result<()> {
+            self.db
+                .insert(self.delimiter.key(path), value)
+                .map_err(sled_error)?;
+            Ok(())
+        }
+
+        async fn delete(&self, path: &str) -> Result<()> {
+            self.db
+                .remove(self.delimiter.key(path))
+                .map_err(sled_error)?;
+            Ok(())
+        }
+
+        async fn scan(&self, path: &str) -> Result<Vec<String>> {
+            self.db
+                .scan_prefix(self.delimiter.key(path))
+                .keys()
+                .map(|key| {
+                    let key = key.map_err(sled_error)?;
+                    Ok(self.delimiter.path(&String::from_utf8_lossy(&key)))
+                })
+                .collect()
+        }
+    }
+
+    fn sled_error(err: sled::Error) -> Error {
+        Error::new(ErrorKind::Unexpected, "sled error").set_source(err)
+    }
+}
+
+#[cfg(feature = "rocksdb")]
+pub(super) use self::rocksdb_store::Rocksdb;
+
+#[cfg(feature = "rocksdb")]
+mod rocksdb_store {
+    use super::{kv, Delimiter};
+
+    use async_trait::async_trait;
+    use opendal::raw::AccessorCapability;
+    use opendal::{Error, ErrorKind, Result, Scheme};
+    use rocksdb::{Options, DB};
+
+    use std::collections::HashMap;
+    use std::fmt::{self, Debug, Formatter};
+
+    pub(in crate::dal) struct Rocksdb {
+        db: DB,
+        delimiter: Delimiter,
+    }
+
+    impl Debug for Rocksdb {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            f.debug_struct("Rocksdb")
+                .field("datadir", &self.db.path())
+                .field("delimiter", &self.delimiter)
+                .finish()
+        }
+    }
+
+    impl Rocksdb {
+        /// Open the existing store at the `datadir` of the configuration.
+        pub(in crate::dal) fn new(config: &HashMap<String, String>) -> Result<Self> {
+            let datadir = config.get("datadir").cloned().ok_or_else(|| {
+                Error::new(ErrorKind::ConfigInvalid, "datadir is required but not set")
+            })?;
+            let mut opts = Options::default();
+            opts.create_if_missing(false);
+            let db = DB::open(&opts, &datadir).map_err(|err| {
+                Error::new(ErrorKind::ConfigInvalid, "the store could not be opened")
+                    .with_context("datadir", datadir.clone())
+                    .set_source(err)
+            })?;
+            Ok(Self {
+                db,
+                delimiter: Delimiter::new(config, '/')
+                    .map_err(|err| Error::new(ErrorKind::ConfigInvalid, &err.to_string()))?,
+            })
+        }
+    }
+
+    #[async_trait]
+    impl kv::Adapter for Rocksdb {
+        fn metadata(&self) -> kv::Metadata {
+            kv::Metadata::new(
+                Scheme::Rocksdb,
+                &self.db.path().to_string_lossy(),
+                AccessorCapability::Read | AccessorCapability::Write | AccessorCapability::Scan,
+            )
+        }
+
+        async fn get(&self, path: &str) -> Result<Option<Vec<u8>>> {
+            self.db.get(self.delimiter.key(path)).map_err(rocksdb_error)
+        }
+
+        async fn set(&self, path: &str, value: &[u8]) -> Result<()> {
+            self.db
+                .put(self.delimiter.key(path), value)
+                .map_err(rocksdb_error)
+        }
+
+        async fn delete(&self, path: &str) -> Result<()> {
+            self.db
+                .delete(self.delimiter.key(path))
+                .map_err(rocksdb_error)
+        }
+
+        /// Iterate over the keys from the key of `path` on, the keys are sorted so the ones
+        /// starting with it come first.
+        async fn scan(&self, path: &str) -> Result<Vec<String>> {
+            let prefix = self.delimiter.key(path);
+            let mut it = self.db.raw_iterator();
+            it.seek(&prefix);
+            let mut paths = vec![];
+            while let Some(key) = it.key().filter(|key| key.starts_with(prefix.as_bytes())) {
+                paths.push(self.delimiter.path(&String::from_utf8_lossy(key)));
+                it.next();
+            }
+            it.status().map_err(rocksdb_error)?;
+            Ok(paths)
+        }
+    }
+
+    fn rocksdb_error(err: rocksdb::Error) -> Error {
+        Error::new(ErrorKind::Unexpected, "rocksdb error").set_source(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Delimiter;
+
+    use std::collections::HashMap;
+    use std::io::ErrorKind;
+
+    #[test]
+    fn test_delimiter() {
+        let redis = Delimiter::new(&HashMap::new(), ':').unwrap();
+        assert_eq!("user:42:name", redis.key("user/42/name"));
+        assert_eq!("user/42/", redis.path("user:42:"));
+
+        let config = HashMap::from([("delimiter".to_string(), ".".to_string())]);
+        let sled = Delimiter::new(&config, '/').unwrap();
+        assert_eq!("app.log.level", sled.key("app/log/level"));
+
+        let config = HashMap::from([("delimiter".to_string(), "::".to_string())]);
+        let err = Delimiter::new(&config, ':').unwrap_err();
+        assert_eq!(ErrorKind::InvalidInput, err.kind());
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_split_datadir() {
+        use super::split_datadir;
+        use assert_fs::prelude::*;
+        use opendal::Scheme;
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("app/conf").touch().unwrap();
+
+        let (datadir, inner) =
+            split_datadir(Scheme::Sled, &dir.path().join("app/user/"), "conf").unwrap();
+        assert_eq!(dir.path().join("app"), datadir);
+        assert_eq!("user/", inner);
+
+        let (datadir, inner) =
+            split_datadir(Scheme::Sled, &dir.path().join("app"), "conf").unwrap();
+        assert_eq!(dir.path().join("app"), datadir);
+        assert_eq!("", inner);
+
+        let err = split_datadir(Scheme::Sled, &dir.path().join("app"), "CURRENT").unwrap_err();
+        assert_eq!(ErrorKind::NotFound, err.kind());
+    }
+
+    #[cfg(feature = "sled")]
+    #[tokio::test]
+    async fn test_list_sled() {
+        use crate::config_file::Config;
+        use crate::dal::{Uri, DAL};
+        use crate::flags::{Block, Blocks, Flags};
+        use std::path::Path;
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        {
+            let db = sled::open(dir.path()).unwrap();
+            for key in ["app.log.level", "app.log.file", "app.name", "version"] {
+                db.insert(key, "value").unwrap();
+            }
+            db.flush().unwrap();
+        }
+        let config = Config::from_yaml(&format!(
+            "profiles: {{app: {{type: sled, datadir: '{}', delimiter: '.'}}}}",
+            dir.path().display()
+        ))
+        .unwrap();
+        let uri = Uri::parse(Path::new("app:/app/"), &config).unwrap();
+        // sled releases the lock of the store from its flusher thread, shortly after the drop.
+        let dal = (0..50)
+            .find_map(|_| {
+                let dal = DAL::new(Path::new("/"), &uri, 1).ok();
+                if dal.is_none() {
+                    std::thread::sleep(std::time::Duration::from_millis(20));
+                }
+                dal
+            })
+            .unwrap();
+
+        let meta = dal.from_path(&uri.path).await.unwrap();
+        let flags = Flags {
+            blocks: Blocks(vec![Block::Size, Block::Name]),
+            ..Flags::default()
+        };
+        let (mut metas, _) = dal.recurse_into(&meta, 1, &flags).await.unwrap();
+        metas.sort_by_key(|meta| meta.path.clone());
+        let names: Vec<_> = metas
+            .iter()
+            .map(|m| m.name().file_name().to_string())
+            .collect();
+        assert_eq!(vec!["log", "name"], names);
+        assert!(metas[0].file_type().is_dirlike());
+        assert_eq!(Some(5), metas[1].size().map(|size| size.get_bytes()));
+        assert_eq!(
+            b"value".to_vec(),
+            dal.read(Path::new("app/log/level")).await.unwrap()
+        );
+    }
+}
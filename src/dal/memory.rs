@@ -0,0 +1,204 @@
+//! This module defines the store of the `memory://` service, shared by the whole process so that
+//! it can be [seed]ed with a fixture before being listed, e.g. with
+//! `dl --seed fixture.yaml -l memory:///`, to run the whole listing without a disk or a network.
+
+use crate::flags::filters::parse_time;
+
+use once_cell::sync::Lazy;
+use opendal::{services, Metadata, Metakey, Operator};
+use serde::Deserialize;
+
+use flagset::FlagSet;
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Error, ErrorKind};
+use std::path::Path;
+use std::sync::RwLock;
+use std::time::SystemTime;
+
+static STORE: Lazy<Store> = Lazy::new(|| Store {
+    op: Operator::from_map::<services::Memory>(HashMap::new())
+        .expect("the memory service always builds")
+        .finish(),
+    seeded: RwLock::new(HashMap::new()),
+});
+
+struct Store {
+    op: Operator,
+    /// The metadata of the seeded files which the memory service does not keep, by key.
+    seeded: RwLock<HashMap<String, Seeded>>,
+}
+
+#[derive(Default)]
+struct Seeded {
+    size: Option<u64>,
+    modified: Option<SystemTime>,
+    content_type: Option<String>,
+    etag: Option<String>,
+}
+
+/// An entry of a fixture file, which is a YAML list of them:
+///
+/// ```yaml
+/// - path: logs/app.log
+///   size: 1048576
+///   modified: 2026-01-01T12:00:00Z
+///   content-type: text/plain
+/// - path: logs/archive/
+/// - path: README.md
+///   content: "# Hello"
+/// ```
+///
+/// A path ending with a `/` is a directory. A file holds its `content`, empty by default, and is
+/// displayed with its `size` instead of the length of its content when one is given.
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct FixtureEntry {
+    path: String,
+    content: Option<String>,
+    size: Option<u64>,
+    /// A date or a duration ago, like the ones of `--newer`.
+    modified: Option<String>,
+    content_type: Option<String>,
+    etag: Option<String>,
+}
+
+/// The operator of the `memory` service, the same one for every [DAL](super::DAL).
+pub(super) fn operator() -> Operator {
+    STORE.op.clone()
+}
+
+/// Write the entries of the YAML fixture at `path` into the `memory` service.
+///
+/// # Errors
+///
+/// If the fixture can not be read or parsed, or if an entry can not be written.
+pub async fn seed(path: &Path) -> io::Result<()> {
+    let fixture = fs::read_to_string(path)?;
+    let entries: Vec<FixtureEntry> = serde_yaml::from_str(&fixture)
+        .map_err(|err| Error::new(ErrorKind::InvalidData, err.to_string()))?;
+
+    for entry in entries {
+        let key = entry.path.trim_start_matches('/');
+        if key.ends_with('/') {
+            STORE.op.create_dir(key).await?;
+            continue;
+        }
+        let modified = entry
+            .modified
+            .map(|modified| parse_time(&modified))
+            .transpose()
+            .map_err(|err| Error::new(ErrorKind::InvalidData, format!("{key}: {err}")))?;
+        let content = entry.content.unwrap_or_default();
+        STORE.op.write(key, content.into_bytes()).await?;
+
+        let seeded = Seeded {
+            size: entry.size,
+            modified: modified.map(SystemTime::from),
+            content_type: entry.content_type,
+            etag: entry.etag,
+        };
+        STORE
+            .seeded
+            .write()
+            .expect("the seeded metadata is never poisoned")
+            .insert(key.to_string(), seeded);
+    }
+    Ok(())
+}
+
+/// Complete the metadata the memory service returns for `path` with what has been seeded, and
+/// narrow the fetched `keys` down to what is known: the service itself only knows the mode and
+/// the length.
+pub(super) fn with_seeded(
+    path: &str,
+    meta: Metadata,
+    keys: FlagSet<Metakey>,
+) -> (Metadata, FlagSet<Metakey>) {
+    if meta.is_dir() {
+        return (meta, keys);
+    }
+
+    let mut meta = meta;
+    let known = Metakey::Mode | Metakey::ContentLength;
+    let mut keys = if keys.contains(Metakey::Complete) {
+        known
+    } else {
+        keys & known
+    };
+    let seeded = STORE
+        .seeded
+        .read()
+        .expect("the seeded metadata is never poisoned");
+    let seeded = match seeded.get(path.trim_start_matches('/')) {
+        Some(seeded) => seeded,
+        None => return (meta, keys),
+    };
+    if let Some(size) = seeded.size {
+        meta = meta.with_content_length(size);
+        keys |= Metakey::ContentLength;
+    }
+    if let Some(modified) = seeded.modified {
+        meta = meta.with_last_modified(modified.into());
+        keys |= Metakey::LastModified;
+    }
+    if let Some(content_type) = &seeded.content_type {
+        meta = meta.with_content_type(content_type.clone());
+        keys |= Metakey::ContentType;
+    }
+    if let Some(etag) = &seeded.etag {
+        meta = meta.with_etag(etag.clone());
+        keys |= Metakey::Etag;
+    }
+    (meta, keys)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{seed, with_seeded};
+
+    use assert_fs::prelude::*;
+    use opendal::{EntryMode, Metadata, Metakey};
+
+    #[tokio::test]
+    async fn test_seed() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let fixture = dir.child("fixture.yaml");
+        fixture
+            .write_str(
+                "- path: seed/a.txt\n  size: 2048\n  modified: 2026-01-01\n  etag: abc\n\
+                 - path: seed/b.txt\n  content: hello\n\
+                 - path: seed/empty/\n",
+            )
+            .unwrap();
+        seed(fixture.path()).await.unwrap();
+
+        let op = super::operator();
+        assert_eq!(b"hello".to_vec(), op.read("seed/b.txt").await.unwrap());
+        assert!(op.stat("seed/empty/").await.unwrap().is_dir());
+
+        let meta = Metadata::new(EntryMode::FILE).with_content_length(0);
+        let (meta, keys) = with_seeded("/seed/a.txt", meta, Metakey::Complete.into());
+        assert_eq!(2048, meta.content_length());
+        assert_eq!(Some("abc"), meta.etag());
+        assert!(meta.last_modified().is_some());
+        assert!(keys.contains(Metakey::LastModified) && !keys.contains(Metakey::ContentType));
+    }
+
+    #[tokio::test]
+    async fn test_seed_invalid() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let fixture = dir.child("fixture.yaml");
+        fixture
+            .write_str("- path: seed-invalid/a\n  colour: red\n")
+            .unwrap();
+        assert!(seed(fixture.path()).await.is_err());
+
+        fixture
+            .write_str("- path: seed-invalid/a\n  modified: yesterday\n")
+            .unwrap();
+        assert!(seed(fixture.path()).await.is_err());
+        assert!(super::operator().stat("seed-invalid/a").await.is_err());
+    }
+}
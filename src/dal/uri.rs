@@ -0,0 +1,613 @@
+//! This module defines the [Uri] struct, which splits an input given by the user into the
+//! service to access, the bucket or host of that service and the path inside of it.
+
+#[cfg(any(feature = "sled", feature = "rocksdb"))]
+use super::kv::split_datadir;
+use crate::config_file::{Config, Credential, Profile};
+
+use opendal::Scheme;
+
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io::{self, Error, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+const SCHEME_SEPARATOR: &str = "://";
+
+/// An input parsed into the parts needed to build an [Operator](opendal::Operator).
+///
+/// Inputs without a scheme, like `src/main.rs` or `/tmp`, are plain paths of the local file
+/// system. Everything else is expected to look like `scheme://authority/path`, where the
+/// authority is the bucket (or container) of object storages and the host of HTTP based services.
+/// `fs://` and `memory://` do not have an authority, so `fs:///tmp` is the same as `/tmp`.
+///
+/// Inputs like `<name>:/path` address the [Profile] `name` of the [Config], its service
+/// configuration is kept in `options`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Uri {
+    pub scheme: Scheme,
+    pub authority: Option<String>,
+    pub path: PathBuf,
+    pub options: HashMap<String, String>,
+    tls: bool,
+}
+
+impl Uri {
+    /// Parse an input into a `Uri`, resolving the profiles of the [Config].
+    ///
+    /// # Errors
+    ///
+    /// This returns an [Error] when the scheme is not supported, when a service which needs
+    /// an authority is given none, like in `s3:///path`, or when the credential of a profile can
+    /// not be read.
+    pub fn parse(input: &Path, config: &Config) -> io::Result<Self> {
+        let input_str = input.to_string_lossy();
+        let (raw_scheme, rest) = match input_str.split_once(SCHEME_SEPARATOR) {
+            Some((raw_scheme, rest)) if is_scheme(raw_scheme) => (raw_scheme, rest),
+            _ => {
+                return match config.profile_of(input) {
+                    Some((name, profile)) => {
+                        Self::from_profile(profile, &input_str[name.len() + 1..])
+                    }
+                    None => Ok(Self::local(input)),
+                }
+            }
+        };
+
+        let scheme = Scheme::from_str(raw_scheme)?;
+        let tls = raw_scheme.eq_ignore_ascii_case("https");
+        match scheme {
+            Scheme::Fs => Ok(Self {
+                scheme,
+                authority: None,
+                path: PathBuf::from(rest),
+                options: HashMap::new(),
+                tls,
+            }),
+            Scheme::Memory => Ok(Self {
+                scheme,
+                authority: None,
+                path: root_if_empty(rest),
+                options: HashMap::new(),
+                tls,
+            }),
+            Scheme::Azblob
+            | Scheme::Azdfs
+            | Scheme::Gcs
+            | Scheme::Obs
+            | Scheme::Oss
+            | Scheme::S3
+            | Scheme::Http
+            | Scheme::Ipmfs
+            | Scheme::Webdav
+            | Scheme::Webhdfs => {
+                let (authority, path) = rest.split_once('/').unwrap_or((rest, ""));
+                if authority.is_empty() {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!("{input_str}: missing bucket or host"),
+                    ));
+                }
+
+                let mut options = HashMap::new();
+                if let (Scheme::S3, Some(s3)) = (scheme, &config.s3) {
+                    let settings = [
+                        ("endpoint", s3.endpoint.clone()),
+                        ("region", s3.region.clone()),
+                        ("path_style", s3.path_style.map(|v| v.to_string())),
+                        ("anonymous", s3.anonymous.map(|v| v.to_string())),
+                    ];
+                    insert_settings(&mut options, settings, s3.credential.as_ref())?;
+                }
+
+                Ok(Self {
+                    scheme,
+                    authority: Some(authority.to_string()),
+                    path: root_if_empty(path),
+                    options,
+                    tls,
+                })
+            }
+            #[cfg(feature = "redis")]
+            Scheme::Redis => {
+                let (authority, path) = rest.split_once('/').unwrap_or((rest, ""));
+                if authority.is_empty() {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!("{input_str}: missing host"),
+                    ));
+                }
+
+                let (db, path) = split_database(path);
+                let mut options = HashMap::new();
+                if let Some(db) = db {
+                    options.insert("db".to_string(), db.to_string());
+                }
+                Ok(Self {
+                    scheme,
+                    authority: Some(authority.to_string()),
+                    path: root_if_empty(path),
+                    options,
+                    tls,
+                })
+            }
+            #[cfg(feature = "sled")]
+            Scheme::Sled => Self::store(scheme, rest, "conf"),
+            #[cfg(feature = "rocksdb")]
+            Scheme::Rocksdb => Self::store(scheme, rest, "CURRENT"),
+            _ => Err(Error::new(
+                ErrorKind::Unsupported,
+                format!("{input_str}: unsupported scheme {raw_scheme}"),
+            )),
+        }
+    }
+
+    /// The `Uri` of an embedded key-value store, the directory of which holds the `marker` file.
+    #[cfg(any(feature = "sled", feature = "rocksdb"))]
+    fn store(scheme: Scheme, rest: &str, marker: &str) -> io::Result<Self> {
+        let (datadir, path) = split_datadir(scheme, Path::new(rest), marker)?;
+        let datadir = datadir.to_string_lossy().to_string();
+        Ok(Self {
+            scheme,
+            authority: None,
+            path: root_if_empty(&path),
+            options: HashMap::from([("datadir".to_string(), datadir)]),
+            tls: false,
+        })
+    }
+
+    fn local(path: &Path) -> Self {
+        Self {
+            scheme: Scheme::Fs,
+            authority: None,
+            path: path.to_path_buf(),
+            options: HashMap::new(),
+            tls: false,
+        }
+    }
+
+    fn from_profile(profile: &Profile, path: &str) -> io::Result<Self> {
+        let scheme = Scheme::from_str(&profile.service)?;
+        if let Scheme::Custom(service) = scheme {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                format!("unsupported profile type {service}"),
+            ));
+        }
+
+        let mut options = HashMap::new();
+        let settings = [
+            ("root", profile.root.clone()),
+            ("endpoint", profile.endpoint.clone()),
+            ("region", profile.region.clone()),
+            ("path_style", profile.path_style.map(|v| v.to_string())),
+            ("anonymous", profile.anonymous.map(|v| v.to_string())),
+            ("datadir", profile.datadir.clone()),
+            ("delimiter", profile.delimiter.clone()),
+        ];
+        insert_settings(&mut options, settings, profile.credential.as_ref())?;
+
+        #[cfg(feature = "redis")]
+        let path = if scheme == Scheme::Redis {
+            let (db, path) = split_database(path.trim_start_matches('/'));
+            if let Some(db) = db {
+                options.insert("db".to_string(), db.to_string());
+            }
+            path
+        } else {
+            path
+        };
+
+        Ok(Self {
+            scheme,
+            authority: profile.bucket.clone(),
+            path: root_if_empty(path),
+            options,
+            tls: profile.service.eq_ignore_ascii_case("https"),
+        })
+    }
+
+    /// The endpoint of HTTP based services and of Redis, built from the authority.
+    ///
+    /// Only `https://` inputs are served over TLS, every other scheme uses plain `http`.
+    pub fn endpoint(&self) -> Option<String> {
+        #[cfg(feature = "redis")]
+        if self.scheme == Scheme::Redis {
+            return self.authority.as_ref().map(|host| format!("tcp://{host}"));
+        }
+        if !matches!(
+            self.scheme,
+            Scheme::Http | Scheme::Ipmfs | Scheme::Webdav | Scheme::Webhdfs
+        ) {
+            return None;
+        }
+
+        let protocol = if self.tls { "https" } else { "http" };
+        self.authority
+            .as_ref()
+            .map(|host| format!("{protocol}://{host}"))
+    }
+}
+
+/// Check whether the text before `://` is a scheme as defined by RFC 3986.
+///
+/// Single letters are rejected, so that Windows drive letters are never mistaken for a scheme.
+fn is_scheme(s: &str) -> bool {
+    let mut chars = s.chars();
+    s.len() > 1
+        && matches!(chars.next(), Some(c) if c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.')
+}
+
+/// Insert the `settings` which are set, and the keys of the `credential`, into the service
+/// `options`.
+fn insert_settings<const N: usize>(
+    options: &mut HashMap<String, String>,
+    settings: [(&str, Option<String>); N],
+    credential: Option<&Credential>,
+) -> io::Result<()> {
+    for (key, value) in settings {
+        if let Some(value) = value {
+            options.insert(key.to_string(), value);
+        }
+    }
+
+    match credential {
+        Some(Credential::Static {
+            access_key_id,
+            secret_access_key,
+        }) => {
+            options.insert("access_key_id".into(), access_key_id.clone());
+            options.insert("secret_access_key".into(), secret_access_key.clone());
+        }
+        Some(Credential::Env {
+            access_key_id,
+            secret_access_key,
+        }) => {
+            options.insert("access_key_id".into(), read_env(access_key_id)?);
+            options.insert("secret_access_key".into(), read_env(secret_access_key)?);
+        }
+        Some(Credential::AwsProfile(name)) => {
+            let path = env::var_os("AWS_SHARED_CREDENTIALS_FILE")
+                .map(PathBuf::from)
+                .or_else(|| dirs::home_dir().map(|home| home.join(".aws").join("credentials")))
+                .ok_or_else(|| Error::new(ErrorKind::NotFound, "no home directory"))?;
+            let mut keys = read_aws_profile(&path, name)?;
+            let mut take = |key: &str| {
+                keys.remove(key).ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::InvalidData,
+                        format!("{}: profile {name} has no {key}", path.display()),
+                    )
+                })
+            };
+            options.insert("access_key_id".into(), take("aws_access_key_id")?);
+            options.insert("secret_access_key".into(), take("aws_secret_access_key")?);
+            if let Ok(token) = take("aws_session_token") {
+                options.insert("security_token".into(), token);
+            }
+        }
+        Some(Credential::Basic { username, password }) => {
+            options.insert("username".into(), username.clone());
+            options.insert("password".into(), password.clone());
+        }
+        Some(Credential::Bearer(token)) => {
+            options.insert("token".into(), token.clone());
+        }
+        None => {}
+    }
+    Ok(())
+}
+
+/// The keys of the `[name]` profile of an AWS shared credentials file, which is an INI file.
+fn read_aws_profile(path: &Path, name: &str) -> io::Result<HashMap<String, String>> {
+    let content = fs::read_to_string(path)
+        .map_err(|err| Error::new(err.kind(), format!("{}: {err}", path.display())))?;
+
+    let mut found = false;
+    let mut in_profile = false;
+    let mut keys = HashMap::new();
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            in_profile = section.trim() == name;
+            found |= in_profile;
+        } else if let (true, Some((key, value))) = (in_profile, line.split_once('=')) {
+            keys.insert(key.trim().to_string(), value.trim().to_string());
+        }
+    }
+
+    if !found {
+        return Err(Error::new(
+            ErrorKind::NotFound,
+            format!("{}: profile {name} not found", path.display()),
+        ));
+    }
+    Ok(keys)
+}
+
+fn read_env(key: &str) -> io::Result<String> {
+    env::var(key).map_err(|err| Error::new(ErrorKind::NotFound, format!("{key}: {err}")))
+}
+
+/// Split the Redis database, the first segment of the path when it is a number, from the rest
+/// of the path: `3/user/` is `user/` in the database 3.
+#[cfg(feature = "redis")]
+fn split_database(path: &str) -> (Option<&str>, &str) {
+    let (first, rest) = path.split_once('/').unwrap_or((path, ""));
+    if !first.is_empty() && first.bytes().all(|b| b.is_ascii_digit()) {
+        (Some(first), rest)
+    } else {
+        (None, path)
+    }
+}
+
+/// The root of services without directories is `/`, an empty path would not be displayable.
+fn root_if_empty(path: &str) -> PathBuf {
+    if path.is_empty() {
+        PathBuf::from("/")
+    } else {
+        PathBuf::from(path)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{read_aws_profile, Uri};
+
+    use crate::config_file::Config;
+
+    use assert_fs::prelude::*;
+    use opendal::Scheme;
+    use std::io::ErrorKind;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn test_parse_relative_path() {
+        let uri = Uri::parse(Path::new("src/main.rs"), &Config::with_none()).unwrap();
+        assert_eq!(Scheme::Fs, uri.scheme);
+        assert_eq!(None, uri.authority);
+        assert_eq!(PathBuf::from("src/main.rs"), uri.path);
+    }
+
+    #[test]
+    fn test_parse_absolute_path() {
+        let uri = Uri::parse(Path::new("/tmp"), &Config::with_none()).unwrap();
+        assert_eq!(Scheme::Fs, uri.scheme);
+        assert_eq!(PathBuf::from("/tmp"), uri.path);
+    }
+
+    #[test]
+    fn test_parse_path_with_colon() {
+        let uri = Uri::parse(Path::new("a:b/c"), &Config::with_none()).unwrap();
+        assert_eq!(Scheme::Fs, uri.scheme);
+        assert_eq!(PathBuf::from("a:b/c"), uri.path);
+    }
+
+    #[test]
+    fn test_parse_fs() {
+        let uri = Uri::parse(Path::new("fs:///tmp"), &Config::with_none()).unwrap();
+        assert_eq!(Scheme::Fs, uri.scheme);
+        assert_eq!(None, uri.authority);
+        assert_eq!(PathBuf::from("/tmp"), uri.path);
+    }
+
+    #[test]
+    fn test_parse_memory() {
+        let uri = Uri::parse(Path::new("memory://"), &Config::with_none()).unwrap();
+        assert_eq!(Scheme::Memory, uri.scheme);
+        assert_eq!(PathBuf::from("/"), uri.path);
+    }
+
+    #[test]
+    fn test_parse_s3() {
+        let uri = Uri::parse(Path::new("s3://bucket/logs/2023/"), &Config::with_none()).unwrap();
+        assert_eq!(Scheme::S3, uri.scheme);
+        assert_eq!(Some("bucket".to_string()), uri.authority);
+        assert_eq!(PathBuf::from("logs/2023/"), uri.path);
+    }
+
+    #[test]
+    fn test_parse_s3_bucket_only() {
+        let uri = Uri::parse(Path::new("S3://bucket"), &Config::with_none()).unwrap();
+        assert_eq!(Scheme::S3, uri.scheme);
+        assert_eq!(Some("bucket".to_string()), uri.authority);
+        assert_eq!(PathBuf::from("/"), uri.path);
+    }
+
+    #[test]
+    fn test_parse_missing_bucket() {
+        let err = Uri::parse(Path::new("s3:///logs"), &Config::with_none()).unwrap_err();
+        assert_eq!(ErrorKind::InvalidInput, err.kind());
+    }
+
+    #[test]
+    fn test_parse_unsupported() {
+        let err = Uri::parse(Path::new("gopher://host/file"), &Config::with_none()).unwrap_err();
+        assert_eq!(ErrorKind::Unsupported, err.kind());
+    }
+
+    #[test]
+    fn test_endpoint() {
+        let uri = Uri::parse(
+            Path::new("webdav://localhost:8080/dav/"),
+            &Config::with_none(),
+        )
+        .unwrap();
+        assert_eq!(Scheme::Webdav, uri.scheme);
+        assert_eq!(Some("http://localhost:8080".to_string()), uri.endpoint());
+        assert_eq!(PathBuf::from("dav/"), uri.path);
+
+        let uri = Uri::parse(
+            Path::new("https://example.com/releases/"),
+            &Config::with_none(),
+        )
+        .unwrap();
+        assert_eq!(Scheme::Http, uri.scheme);
+        assert_eq!(Some("https://example.com".to_string()), uri.endpoint());
+
+        let uri = Uri::parse(Path::new("s3://bucket/"), &Config::with_none()).unwrap();
+        assert_eq!(None, uri.endpoint());
+    }
+
+    #[cfg(feature = "redis")]
+    #[test]
+    fn test_parse_redis() {
+        let uri = Uri::parse(
+            Path::new("redis://cache:6380/3/user/"),
+            &Config::with_none(),
+        )
+        .unwrap();
+        assert_eq!(Scheme::Redis, uri.scheme);
+        assert_eq!(Some("tcp://cache:6380".to_string()), uri.endpoint());
+        assert_eq!("3", uri.options["db"]);
+        assert_eq!(PathBuf::from("user/"), uri.path);
+
+        let uri = Uri::parse(Path::new("redis://cache/user/"), &Config::with_none()).unwrap();
+        assert_eq!(None, uri.options.get("db"));
+        assert_eq!(PathBuf::from("user/"), uri.path);
+
+        let uri = Uri::parse(Path::new("redis://cache"), &Config::with_none()).unwrap();
+        assert_eq!(PathBuf::from("/"), uri.path);
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_parse_sled() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("app/conf").touch().unwrap();
+
+        let input = format!("sled://{}/app/user/", dir.path().display());
+        let uri = Uri::parse(Path::new(&input), &Config::with_none()).unwrap();
+        assert_eq!(Scheme::Sled, uri.scheme);
+        assert_eq!(
+            dir.path().join("app").to_string_lossy(),
+            uri.options["datadir"]
+        );
+        assert_eq!(PathBuf::from("user/"), uri.path);
+
+        let input = format!("sled://{}/other/", dir.path().display());
+        let err = Uri::parse(Path::new(&input), &Config::with_none()).unwrap_err();
+        assert_eq!(ErrorKind::NotFound, err.kind());
+    }
+
+    #[test]
+    fn test_parse_profile() {
+        let config = Config::from_yaml(
+            r#"---
+profiles:
+  logs:
+    type: s3
+    bucket: logs
+    root: /prod
+    region: us-east-1
+    credential:
+      static:
+        access-key-id: AK
+        secret-access-key: SK
+"#,
+        )
+        .unwrap();
+
+        let uri = Uri::parse(Path::new("logs:/2023/"), &config).unwrap();
+        assert_eq!(Scheme::S3, uri.scheme);
+        assert_eq!(Some("logs".to_string()), uri.authority);
+        assert_eq!(PathBuf::from("/2023/"), uri.path);
+        assert_eq!("/prod", uri.options["root"]);
+        assert_eq!("us-east-1", uri.options["region"]);
+        assert_eq!("AK", uri.options["access_key_id"]);
+        assert_eq!("SK", uri.options["secret_access_key"]);
+
+        let uri = Uri::parse(Path::new("logs:"), &config).unwrap();
+        assert_eq!(PathBuf::from("/"), uri.path);
+
+        let uri = Uri::parse(Path::new("other:/2023/"), &config).unwrap();
+        assert_eq!(Scheme::Fs, uri.scheme);
+        assert_eq!(PathBuf::from("other:/2023/"), uri.path);
+    }
+
+    #[test]
+    fn test_parse_webdav_profile() {
+        let config = Config::from_yaml(
+            r#"---
+profiles:
+  dav:
+    type: webdav
+    endpoint: https://artifacts.local
+    credential:
+      basic:
+        username: ci
+        password: secret
+"#,
+        )
+        .unwrap();
+
+        let uri = Uri::parse(Path::new("dav:/nightly/"), &config).unwrap();
+        assert_eq!(Scheme::Webdav, uri.scheme);
+        assert_eq!(None, uri.endpoint());
+        assert_eq!("https://artifacts.local", uri.options["endpoint"]);
+        assert_eq!("ci", uri.options["username"]);
+        assert_eq!("secret", uri.options["password"]);
+    }
+
+    #[test]
+    fn test_parse_profile_missing_env() {
+        let config = Config::from_yaml(
+            r#"---
+profiles:
+  logs:
+    type: s3
+    credential:
+      env:
+        access-key-id: DILU_TEST_NOT_SET_AK
+        secret-access-key: DILU_TEST_NOT_SET_SK
+"#,
+        )
+        .unwrap();
+
+        let err = Uri::parse(Path::new("logs:/"), &config).unwrap_err();
+        assert_eq!(ErrorKind::NotFound, err.kind());
+    }
+
+    #[test]
+    fn test_parse_s3_section() {
+        let config = Config::from_yaml(
+            "s3: {endpoint: 'http://localhost:9000', path-style: true, anonymous: true}",
+        )
+        .unwrap();
+
+        let uri = Uri::parse(Path::new("s3://bucket/logs/"), &config).unwrap();
+        assert_eq!("http://localhost:9000", uri.options["endpoint"]);
+        assert_eq!("true", uri.options["path_style"]);
+        assert_eq!("true", uri.options["anonymous"]);
+        assert_eq!(None, uri.options.get("region"));
+
+        // Only the S3 inputs use the section.
+        let uri = Uri::parse(Path::new("gcs://bucket/logs/"), &config).unwrap();
+        assert!(uri.options.is_empty());
+    }
+
+    #[test]
+    fn test_read_aws_profile() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let credentials = dir.child("credentials");
+        credentials
+            .write_str(
+                "[default]\naws_access_key_id = DEFAULT\n\n\
+                 # MinIO\n[minio]\naws_access_key_id=AK\naws_secret_access_key = SK\n",
+            )
+            .unwrap();
+
+        let keys = read_aws_profile(credentials.path(), "minio").unwrap();
+        assert_eq!("AK", keys["aws_access_key_id"]);
+        assert_eq!("SK", keys["aws_secret_access_key"]);
+        assert_eq!(2, keys.len());
+
+        let err = read_aws_profile(credentials.path(), "prod").unwrap_err();
+        assert_eq!(ErrorKind::NotFound, err.kind());
+    }
+}
@@ -3,7 +3,7 @@ use crate::dal::Meta;
 use crate::flags::{Block, Display, Flags, HyperlinkOption, Layout};
 use crate::icon::Icons;
 use crate::meta::name::DisplayOption;
-use crate::meta::FileType;
+use crate::meta::Indicator;
 use std::collections::HashMap;
 use term_grid::{Cell, Direction, Filling, Grid, GridOptions};
 use terminal_size::terminal_size;
@@ -28,6 +28,21 @@ pub fn grid(metas: &[Meta], flags: &Flags, colors: &Colors, icons: &Icons) -> St
     )
 }
 
+/// Display `metas` as the entries of a single directory, by their file names.
+pub fn entries(metas: &[Meta], flags: &Flags, colors: &Colors, icons: &Icons) -> String {
+    let term_width = terminal_size().map(|(w, _)| w.0 as usize);
+
+    inner_display_grid(
+        &DisplayOption::FileName,
+        metas,
+        flags,
+        colors,
+        icons,
+        1,
+        term_width,
+    )
+}
+
 pub fn tree(metas: &[Meta], flags: &Flags, colors: &Colors, icons: &Icons) -> String {
     let mut grid = Grid::new(GridOptions {
         filling: Filling::Spaces(1),
@@ -82,7 +97,7 @@ fn inner_display_grid(
     // print the files first.
     for meta in metas {
         // Maybe skip showing the directory meta now; show its contents later.
-        if skip_dirs && matches!(meta.file_type(), FileType::Directory { .. }) {
+        if skip_dirs && meta.lists_content(flags) {
             continue;
         }
 
@@ -134,7 +149,7 @@ fn inner_display_grid(
 
     // print the folder content
     for meta in metas {
-        if meta.sub_metas.len() != 0 {
+        if !meta.sub_metas.is_empty() {
             if should_display_folder_path {
                 output += &display_folder_path(meta);
             }
@@ -228,41 +243,38 @@ fn inner_display_tree(
             });
         }
 
-        // if let Some(content) = &meta.content {
-        //     let new_prefix = if tree_depth_prefix.0 > 0 {
-        //         if idx + 1 != last_idx {
-        //             // is last folder elem
-        //             format!("{}{} ", tree_depth_prefix.1, LINE)
-        //         } else {
-        //             format!("{}{} ", tree_depth_prefix.1, BLANK)
-        //         }
-        //     } else {
-        //         tree_depth_prefix.1.to_string()
-        //     };
-        //
-        //     cells.extend(inner_display_tree(
-        //         content,
-        //         flags,
-        //         colors,
-        //         icons,
-        //         (tree_depth_prefix.0 + 1, &new_prefix),
-        //         padding_rules,
-        //         tree_index,
-        //     ));
-        // }
+        if !meta.sub_metas.is_empty() {
+            let new_prefix = if tree_depth_prefix.0 > 0 {
+                if idx + 1 != last_idx {
+                    // is last folder elem
+                    format!("{}{} ", tree_depth_prefix.1, LINE)
+                } else {
+                    format!("{}{} ", tree_depth_prefix.1, BLANK)
+                }
+            } else {
+                tree_depth_prefix.1.to_string()
+            };
+
+            cells.extend(inner_display_tree(
+                &meta.sub_metas,
+                flags,
+                colors,
+                icons,
+                (tree_depth_prefix.0 + 1, &new_prefix),
+                padding_rules,
+                tree_index,
+            ));
+        }
     }
 
     cells
 }
 
-fn should_display_folder_path(depth: usize, metas: &[Meta], _flags: &Flags) -> bool {
+fn should_display_folder_path(depth: usize, metas: &[Meta], flags: &Flags) -> bool {
     if depth > 0 {
         true
     } else {
-        let folder_number = metas
-            .iter()
-            .filter(|x| matches!(x.file_type(), FileType::Directory))
-            .count();
+        let folder_number = metas.iter().filter(|x| x.lists_content(flags)).count();
 
         folder_number > 1 || folder_number < metas.len()
     }
@@ -292,6 +304,29 @@ fn get_output(
         };
 
         match block {
+            Block::INode => block_vec.push(match meta.inode() {
+                Some(inode) => inode.render(colors),
+                None => colorize_missing("-"),
+            }),
+            Block::Links => block_vec.push(match meta.links() {
+                Some(links) => links.render(colors),
+                None => colorize_missing("-"),
+            }),
+            Block::Permission => match meta.permissions() {
+                Some(permissions) => block_vec.extend([
+                    meta.file_type().render(colors),
+                    permissions.render(colors, flags),
+                ]),
+                None => block_vec.push(colorize_missing("-")),
+            },
+            Block::User => block_vec.push(match meta.owner() {
+                Some(owner) => owner.render_user(colors),
+                None => colorize_missing("-"),
+            }),
+            Block::Group => block_vec.push(match meta.owner() {
+                Some(owner) => owner.render_group(colors),
+                None => colorize_missing("-"),
+            }),
             Block::Size => {
                 let pad = if Layout::Tree == flags.layout && 0 == tree.0 && 0 == i {
                     None
@@ -308,13 +343,30 @@ fn get_output(
                 None => colorize_missing("?"),
             }),
             Block::Date => block_vec.push(meta.modified_date().render(colors, flags)),
-            Block::Name => block_vec.push(meta.name().render(
-                colors,
-                icons,
-                display_option,
-                flags.hyperlink,
-                flags.should_quote,
-            )),
+            Block::Name => {
+                block_vec.extend([
+                    meta.name().render(
+                        colors,
+                        icons,
+                        display_option,
+                        flags.hyperlink,
+                        flags.should_quote,
+                    ),
+                    Indicator::from(meta.file_type()).render(flags),
+                ]);
+                if !(flags.no_symlink.0 || flags.dereference.0 || flags.layout == Layout::Grid) {
+                    if let Some(symlink) = meta.symlink() {
+                        block_vec.push(symlink.render(colors, flags));
+                    }
+                }
+            }
+            Block::ContentType | Block::Etag | Block::ContentMd5 | Block::ContentDisposition => {
+                let pad = padding_rules.get(block).copied().unwrap_or(0);
+                block_vec.push(match metadata(meta, block) {
+                    Some(value) => colors.colorize(format!("{value:<pad$}"), &metadata_elem(block)),
+                    None => colorize_missing(&format!("{:<pad$}", "-")),
+                })
+            }
         };
         strings.push(
             block_vec
@@ -327,6 +379,26 @@ fn get_output(
     strings
 }
 
+/// The value of a [Block] of object metadata, [None] when the service did not return it.
+fn metadata<'a>(meta: &'a Meta, block: &Block) -> Option<&'a str> {
+    match block {
+        Block::ContentType => meta.content_type(),
+        Block::Etag => meta.etag(),
+        Block::ContentMd5 => meta.content_md5(),
+        Block::ContentDisposition => meta.content_disposition(),
+        _ => None,
+    }
+}
+
+fn metadata_elem(block: &Block) -> Elem {
+    match block {
+        Block::Etag => Elem::Etag,
+        Block::ContentMd5 => Elem::ContentMd5,
+        Block::ContentDisposition => Elem::ContentDisposition,
+        _ => Elem::ContentType,
+    }
+}
+
 fn get_visible_width(input: &str, hyperlink: bool) -> usize {
     let mut nb_invisible_char = 0;
 
@@ -367,14 +439,32 @@ fn detect_size_lengths(metas: &[Meta], flags: &Flags) -> usize {
             max_value_length = len;
         }
 
-        // if Layout::Tree == flags.layout {
-        //     if let Some(subs) = &meta.content {
-        //         let sub_length = detect_size_lengths(subs, flags);
-        //         if sub_length > max_value_length {
-        //             max_value_length = sub_length;
-        //         }
-        //     }
-        // }
+        if Layout::Tree == flags.layout {
+            let sub_length = detect_size_lengths(&meta.sub_metas, flags);
+            if sub_length > max_value_length {
+                max_value_length = sub_length;
+            }
+        }
+    }
+
+    max_value_length
+}
+
+fn detect_metadata_lengths(metas: &[Meta], flags: &Flags, block: &Block) -> usize {
+    let mut max_value_length: usize = 0;
+
+    for meta in metas {
+        let len = metadata(meta, block).map_or(1, UnicodeWidthStr::width);
+        if len > max_value_length {
+            max_value_length = len;
+        }
+
+        if Layout::Tree == flags.layout {
+            let sub_length = detect_metadata_lengths(&meta.sub_metas, flags, block);
+            if sub_length > max_value_length {
+                max_value_length = sub_length;
+            }
+        }
     }
 
     max_value_length
@@ -389,6 +479,15 @@ fn get_padding_rules(metas: &[Meta], flags: &Flags) -> HashMap<Block, usize> {
         padding_rules.insert(Block::SizeValue, size_val);
     }
 
+    for block in &flags.blocks.0 {
+        if matches!(
+            block,
+            Block::ContentType | Block::Etag | Block::ContentMd5 | Block::ContentDisposition
+        ) {
+            padding_rules.insert(*block, detect_metadata_lengths(metas, flags, block));
+        }
+    }
+
     padding_rules
 }
 
@@ -398,10 +497,10 @@ mod tests {
     use crate::app::Cli;
     use crate::color;
     use crate::color::Colors;
+    use crate::config_file::Config;
     use crate::flags::{HyperlinkOption, IconOption, IconTheme as FlagTheme};
     use crate::icon::Icons;
     use crate::meta::{FileType, Name};
-    use crate::Config;
     use crate::{flags, sort};
     use assert_fs::prelude::*;
     use clap::Parser;
@@ -573,18 +672,24 @@ mod tests {
         }
     }
 
-    fn sort(metas: &mut Vec<Meta>, sorters: &Vec<(flags::SortOrder, sort::SortFn)>) {
+    async fn list(dir: &Path, depth: usize, flags: &Flags) -> Meta {
+        let uri = crate::dal::Uri::parse(dir, &Config::with_none()).unwrap();
+        let dal = crate::dal::DAL::new(Path::new("/"), &uri, 4).unwrap();
+        let mut meta = dal.from_path(&uri.path).await.unwrap();
+        meta.sub_metas = dal.recurse_into(&meta, depth, flags).await.unwrap().0;
+        meta
+    }
+
+    fn sort(metas: &mut [Meta], sorters: &[(flags::SortOrder, sort::SortFn)]) {
         metas.sort_unstable_by(|a, b| sort::by_meta(sorters, a, b));
 
         for meta in metas {
-            if let Some(ref mut content) = meta.content {
-                sort(content, sorters);
-            }
+            sort(&mut meta.sub_metas, sorters);
         }
     }
 
-    #[test]
-    fn test_display_tree_with_all() {
+    #[tokio::test]
+    async fn test_display_tree_with_all() {
         let argv = ["lsd", "--tree", "--all"];
         let cli = Cli::try_parse_from(argv).unwrap();
         let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
@@ -593,12 +698,7 @@ mod tests {
         dir.child("one.d").create_dir_all().unwrap();
         dir.child("one.d/two").touch().unwrap();
         dir.child("one.d/.hidden").touch().unwrap();
-        let mut metas = Meta::from_path(Path::new(dir.path()), false)
-            .unwrap()
-            .recurse_into(42, &flags)
-            .unwrap()
-            .0
-            .unwrap();
+        let mut metas = list(dir.path(), 42, &flags).await.sub_metas;
         sort(&mut metas, &sort::assemble_sorters(&flags));
         let output = tree(
             &metas,
@@ -616,8 +716,8 @@ mod tests {
     /// dir has a bytes size
     /// empty file has an empty size
     /// `---blocks size,name` can help us for this case
-    #[test]
-    fn test_tree_align_subfolder() {
+    #[tokio::test]
+    async fn test_tree_align_subfolder() {
         let argv = ["lsd", "--tree", "--blocks", "size,name"];
         let cli = Cli::try_parse_from(argv).unwrap();
         let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
@@ -625,12 +725,7 @@ mod tests {
         let dir = assert_fs::TempDir::new().unwrap();
         dir.child("dir").create_dir_all().unwrap();
         dir.child("dir/file").touch().unwrap();
-        let metas = Meta::from_path(Path::new(dir.path()), false)
-            .unwrap()
-            .recurse_into(42, &flags)
-            .unwrap()
-            .0
-            .unwrap();
+        let metas = list(dir.path(), 42, &flags).await.sub_metas;
         let output = tree(
             &metas,
             &flags,
@@ -655,9 +750,9 @@ mod tests {
         );
     }
 
-    #[test]
+    #[tokio::test]
     #[cfg(unix)]
-    fn test_tree_size_first_without_name() {
+    async fn test_tree_size_first_without_name() {
         let argv = ["lsd", "--tree", "--blocks", "size,permission"];
         let cli = Cli::try_parse_from(argv).unwrap();
         let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
@@ -665,12 +760,7 @@ mod tests {
         let dir = assert_fs::TempDir::new().unwrap();
         dir.child("dir").create_dir_all().unwrap();
         dir.child("dir/file").touch().unwrap();
-        let metas = Meta::from_path(Path::new(dir.path()), false)
-            .unwrap()
-            .recurse_into(42, &flags)
-            .unwrap()
-            .0
-            .unwrap();
+        let metas = list(dir.path(), 42, &flags).await.sub_metas;
         let output = tree(
             &metas,
             &flags,
@@ -695,8 +785,8 @@ mod tests {
         );
     }
 
-    #[test]
-    fn test_tree_edge_before_name() {
+    #[tokio::test]
+    async fn test_tree_edge_before_name() {
         let argv = ["lsd", "--tree", "--long"];
         let cli = Cli::try_parse_from(argv).unwrap();
         let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
@@ -704,12 +794,7 @@ mod tests {
         let dir = assert_fs::TempDir::new().unwrap();
         dir.child("one.d").create_dir_all().unwrap();
         dir.child("one.d/two").touch().unwrap();
-        let metas = Meta::from_path(Path::new(dir.path()), false)
-            .unwrap()
-            .recurse_into(42, &flags)
-            .unwrap()
-            .0
-            .unwrap();
+        let metas = list(dir.path(), 42, &flags).await.sub_metas;
         let output = tree(
             &metas,
             &flags,
@@ -720,8 +805,8 @@ mod tests {
         assert!(output.ends_with("└── two\n"));
     }
 
-    #[test]
-    fn test_grid_all_block_headers() {
+    #[tokio::test]
+    async fn test_grid_all_block_headers() {
         let argv = [
             "lsd",
             "--header",
@@ -734,12 +819,7 @@ mod tests {
         let dir = assert_fs::TempDir::new().unwrap();
         dir.child("testdir").create_dir_all().unwrap();
         dir.child("test").touch().unwrap();
-        let metas = Meta::from_path(Path::new(dir.path()), false)
-            .unwrap()
-            .recurse_into(1, &flags)
-            .unwrap()
-            .0
-            .unwrap();
+        let metas = list(dir.path(), 1, &flags).await.sub_metas;
         let output = grid(
             &metas,
             &flags,
@@ -759,20 +839,126 @@ mod tests {
         assert!(output.contains("Links"));
     }
 
-    #[test]
-    fn test_grid_no_header_with_empty_meta() {
+    #[tokio::test]
+    async fn test_grid_metadata_blocks() {
+        let argv = [
+            "lsd",
+            "--header",
+            "--blocks",
+            "content-type,etag,content-md5,content-disposition,name",
+        ];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("test").touch().unwrap();
+        let meta = list(dir.path(), 1, &flags).await;
+        let output = grid(
+            &[meta],
+            &flags,
+            &Colors::new(color::ThemeOption::NoColor),
+            &Icons::new(false, IconOption::Never, FlagTheme::Fancy, " ".to_string()),
+        );
+
+        let header = output.lines().next().unwrap();
+        for name in ["Content Type", "ETag", "Content MD5", "Content Disposition"] {
+            assert!(header.contains(name));
+        }
+        // The local filesystem returns none of them.
+        assert!(output.lines().nth(1).unwrap().starts_with("-"));
+        assert!(output.lines().nth(1).unwrap().ends_with("test"));
+    }
+
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn test_grid_posix_blocks() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let argv = [
+            "lsd",
+            "--blocks",
+            "permission,links,name",
+            "--permission",
+            "octal",
+        ];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("test").touch().unwrap();
+        std::fs::set_permissions(
+            dir.child("test").path(),
+            std::fs::Permissions::from_mode(0o754),
+        )
+        .unwrap();
+        let meta = list(dir.path(), 1, &flags).await;
+        let output = grid(
+            &[meta],
+            &flags,
+            &Colors::new(color::ThemeOption::NoColor),
+            &Icons::new(false, IconOption::Never, FlagTheme::Fancy, " ".to_string()),
+        );
+
+        assert_eq!(".0754 1 test\n", output);
+    }
+
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn test_oneline_symlink_and_indicators() {
+        let argv = ["lsd", "-1", "-F"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("dir").create_dir_all().unwrap();
+        dir.child("link").symlink_to_dir("dir").unwrap();
+        let meta = list(dir.path(), 1, &flags).await;
+        let output = grid(
+            &[meta],
+            &flags,
+            &Colors::new(color::ThemeOption::NoColor),
+            &Icons::new(false, IconOption::Never, FlagTheme::Fancy, " ".to_string()),
+        );
+
+        assert_eq!("dir/\nlink@ ⇒ dir\n", output);
+    }
+
+    #[tokio::test]
+    async fn test_entries() {
+        let argv = ["lsd", "-1"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("one/file").touch().unwrap();
+        dir.child("two").touch().unwrap();
+        let uri = crate::dal::Uri::parse(dir.path(), &Config::with_none()).unwrap();
+        let dal = crate::dal::DAL::new(Path::new("/"), &uri, 4).unwrap();
+        let meta = dal.from_path(&uri.path).await.unwrap();
+        let mut metas = vec![];
+        dal.walk(&meta, 2, &flags, |_, entry| metas.push(entry))
+            .await
+            .unwrap();
+        metas.sort_by_key(|meta| meta.name());
+        let output = entries(
+            &metas,
+            &flags,
+            &Colors::new(color::ThemeOption::NoColor),
+            &Icons::new(false, IconOption::Never, FlagTheme::Fancy, " ".to_string()),
+        );
+
+        assert_eq!("file\none\ntwo\n", output);
+    }
+
+    #[tokio::test]
+    async fn test_grid_no_header_with_empty_meta() {
         let argv = ["lsd", "--header", "-l"];
         let cli = Cli::try_parse_from(argv).unwrap();
         let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
 
         let dir = assert_fs::TempDir::new().unwrap();
         dir.child("testdir").create_dir_all().unwrap();
-        let metas = Meta::from_path(Path::new(dir.path()), false)
-            .unwrap()
-            .recurse_into(1, &flags)
-            .unwrap()
-            .0
-            .unwrap();
+        let metas = list(dir.path(), 1, &flags).await.sub_metas;
         let output = grid(
             &metas,
             &flags,
@@ -1,38 +1,58 @@
 pub mod blocks;
 pub mod color;
+pub mod concurrency;
 pub mod date;
+pub mod dereference;
 pub mod display;
+pub mod filters;
+pub mod format;
 pub mod header;
+pub mod hide_dot_keys;
 pub mod hyperlink;
 pub mod icons;
 pub mod ignore_globs;
+pub mod indicators;
 pub mod layout;
+pub mod permission;
 pub mod recursion;
 pub mod size;
 pub mod sorting;
+pub mod symlink_arrow;
+pub mod symlinks;
 pub mod total_size;
+pub mod total_size_limit;
 
 pub use blocks::Block;
 pub use blocks::Blocks;
 pub use color::Color;
 pub use color::{ColorOption, ThemeOption};
+pub use concurrency::Concurrency;
 pub use date::DateFlag;
+pub use dereference::Dereference;
 pub use display::Display;
+pub use filters::Filters;
+pub use format::Format;
 pub use header::Header;
+pub use hide_dot_keys::HideDotKeys;
 pub use hyperlink::HyperlinkOption;
 pub use icons::IconOption;
 pub use icons::IconSeparator;
 pub use icons::IconTheme;
 pub use icons::Icons;
 pub use ignore_globs::IgnoreGlobs;
+pub use indicators::Indicators;
 pub use layout::Layout;
+pub use permission::PermissionFlag;
 pub use recursion::Recursion;
 pub use size::SizeFlag;
 pub use sorting::DirGrouping;
 pub use sorting::SortColumn;
 pub use sorting::SortOrder;
 pub use sorting::Sorting;
+pub use symlink_arrow::SymlinkArrow;
+pub use symlinks::NoSymlink;
 pub use total_size::TotalSize;
+pub use total_size_limit::TotalSizeLimit;
 
 use crate::app::Cli;
 use crate::config_file::Config;
@@ -48,16 +68,26 @@ pub struct Flags {
     pub blocks: Blocks,
     pub color: Color,
     pub date: DateFlag,
+    pub dereference: Dereference,
     pub display: Display,
+    pub display_indicators: Indicators,
+    pub filters: Filters,
+    pub format: Format,
+    pub hide_dot_keys: HideDotKeys,
     pub icons: Icons,
     pub ignore_globs: IgnoreGlobs,
     pub layout: Layout,
+    pub no_symlink: NoSymlink,
+    pub permission: PermissionFlag,
     pub recursion: Recursion,
     pub size: SizeFlag,
     pub sorting: Sorting,
+    pub symlink_arrow: SymlinkArrow,
     pub total_size: TotalSize,
+    pub total_size_limit: TotalSizeLimit,
     pub hyperlink: HyperlinkOption,
     pub header: Header,
+    pub concurrency: Concurrency,
     pub should_quote: bool,
 }
 
@@ -66,23 +96,33 @@ impl Flags {
     ///
     /// # Errors
     ///
-    /// This can return an [Error], when either the building of the ignore globs or the parsing of
-    /// the recursion depth parameter fails.
+    /// This can return an [Error], when either the building of the ignore globs, the parsing of
+    /// the recursion depth parameter or the parsing of the filters fails.
     pub fn configure_from(cli: &Cli, config: &Config) -> Result<Self, Error> {
         Ok(Self {
             blocks: Blocks::configure_from(cli, config),
             color: Color::configure_from(cli, config),
             date: DateFlag::configure_from(cli, config),
+            dereference: Dereference::configure_from(cli, config),
             display: Display::configure_from(cli, config),
+            display_indicators: Indicators::configure_from(cli, config),
+            filters: Filters::configure_from(cli, config)?,
+            format: Format::configure_from(cli, config),
+            hide_dot_keys: HideDotKeys::configure_from(cli, config),
             layout: Layout::configure_from(cli, config),
+            no_symlink: NoSymlink::configure_from(cli, config),
             size: SizeFlag::configure_from(cli, config),
+            permission: PermissionFlag::configure_from(cli, config),
             icons: Icons::configure_from(cli, config),
             ignore_globs: IgnoreGlobs::configure_from(cli, config)?,
             recursion: Recursion::configure_from(cli, config),
             sorting: Sorting::configure_from(cli, config),
+            symlink_arrow: SymlinkArrow::configure_from(cli, config),
             total_size: TotalSize::configure_from(cli, config),
+            total_size_limit: TotalSizeLimit::configure_from(cli, config),
             hyperlink: HyperlinkOption::configure_from(cli, config),
             header: Header::configure_from(cli, config),
+            concurrency: Concurrency::configure_from(cli, config),
             should_quote: true,
         })
     }
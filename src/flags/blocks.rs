@@ -4,8 +4,11 @@
 use super::Configurable;
 use crate::app::Cli;
 use crate::config_file::Config;
+use crate::dal::Uri;
 use crate::print_error;
 
+use opendal::Scheme;
+
 use std::convert::TryFrom;
 
 /// A struct to hold a [Vec] of [Block]s and to provide methods to create it.
@@ -18,12 +21,31 @@ impl Blocks {
     /// It contains the [Block]s [Permission](Block::Permission), [User](Block::User),
     /// [Group](Block::Group), [Size](Block::Size), [Date](Block::Date) and [Name](Block::Name).
     fn long() -> Self {
-        Self(vec![Block::Date, Block::Size, Block::Name])
+        Self(vec![
+            Block::Permission,
+            Block::User,
+            Block::Group,
+            Block::Size,
+            Block::Date,
+            Block::Name,
+        ])
     }
 
     pub fn displays_size(&self) -> bool {
         self.0.contains(&Block::Size)
     }
+
+    /// Checks whether `self` already contains a [Block] of variant [INode](Block::INode).
+    fn contains_inode(&self) -> bool {
+        self.0.contains(&Block::INode)
+    }
+
+    /// Prepends a [Block] of variant [INode](Block::INode) to `self`, unless it already has one.
+    fn optional_prepend_inode(&mut self) {
+        if !self.contains_inode() {
+            self.0.insert(0, Block::INode)
+        }
+    }
 }
 
 impl Configurable<Self> for Blocks {
@@ -34,6 +56,9 @@ impl Configurable<Self> for Blocks {
     /// - [from_config](Blocks::from_config)
     /// - [long](Blocks::long)
     ///
+    /// The blocks of the long layout which only the local filesystem fills, like the
+    /// permissions, are left out when no input is on it. The ones given on the [Cli] are kept.
+    ///
     /// No matter if the "long" argument was passed, if the "inode" argument is passed and the
     /// `Blocks` does not contain a [Block] of variant [INode](Block::INode) yet, one is prepended
     /// to the returned value.
@@ -48,12 +73,22 @@ impl Configurable<Self> for Blocks {
             if let Some(value) = Self::from_config(config) {
                 blocks = value;
             }
+            if !lists_local(cli, config) {
+                blocks.0.retain(|block| !block.is_local());
+                if blocks.0.is_empty() {
+                    blocks = Default::default();
+                }
+            }
         }
 
         if let Some(value) = Self::from_cli(cli) {
             blocks = value;
         }
 
+        if cli.inode {
+            blocks.optional_prepend_inode();
+        }
+
         blocks
     }
 
@@ -99,6 +134,13 @@ impl Configurable<Self> for Blocks {
     }
 }
 
+/// Whether some of the inputs are on the local filesystem.
+fn lists_local(cli: &Cli, config: &Config) -> bool {
+    cli.inputs
+        .iter()
+        .any(|input| Uri::parse(input, config).is_ok_and(|uri| uri.scheme == Scheme::Fs))
+}
+
 /// The default value for `Blocks` contains a [Vec] of [Name](Block::Name).
 impl Default for Blocks {
     fn default() -> Self {
@@ -109,19 +151,45 @@ impl Default for Blocks {
 /// A block of data to show.
 #[derive(Clone, Debug, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum Block {
+    Permission,
+    User,
+    Group,
     Size,
     SizeValue,
     Date,
     Name,
+    INode,
+    Links,
+    ContentType,
+    Etag,
+    ContentMd5,
+    ContentDisposition,
 }
 
 impl Block {
+    /// Whether only the local filesystem has the data of the block, object stores have none.
+    fn is_local(&self) -> bool {
+        matches!(
+            self,
+            Block::Permission | Block::User | Block::Group | Block::INode | Block::Links
+        )
+    }
+
     pub fn get_header(&self) -> &'static str {
         match self {
+            Block::Permission => "Permissions",
+            Block::User => "User",
+            Block::Group => "Group",
             Block::Size => "Size",
             Block::SizeValue => "SizeValue",
             Block::Date => "Date Modified",
             Block::Name => "Name",
+            Block::INode => "INode",
+            Block::Links => "Links",
+            Block::ContentType => "Content Type",
+            Block::Etag => "ETag",
+            Block::ContentMd5 => "Content MD5",
+            Block::ContentDisposition => "Content Disposition",
         }
     }
 }
@@ -131,10 +199,19 @@ impl TryFrom<&str> for Block {
 
     fn try_from(string: &str) -> Result<Self, Self::Error> {
         match string {
+            "permission" => Ok(Self::Permission),
+            "user" => Ok(Self::User),
+            "group" => Ok(Self::Group),
             "size" => Ok(Self::Size),
             "size_value" => Ok(Self::SizeValue),
             "date" => Ok(Self::Date),
             "name" => Ok(Self::Name),
+            "inode" => Ok(Self::INode),
+            "links" => Ok(Self::Links),
+            "content-type" => Ok(Self::ContentType),
+            "etag" => Ok(Self::Etag),
+            "content-md5" => Ok(Self::ContentMd5),
+            "content-disposition" => Ok(Self::ContentDisposition),
             _ => Err(format!("Not a valid block name: {string}")),
         }
     }
@@ -173,6 +250,31 @@ mod test_blocks {
         assert_eq!(result, target);
     }
 
+    #[test]
+    fn test_configure_from_with_long_off_the_local_filesystem() {
+        let argv = ["lsd", "--long", "memory:///logs/"];
+        let target = Blocks(vec![Block::Size, Block::Date, Block::Name]);
+
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let result = Blocks::configure_from(&cli, &Config::with_none());
+        assert_eq!(result, target);
+
+        let mut c = Config::with_none();
+        c.blocks = Some(vec!["permission".into(), "links".into(), "etag".into()]);
+        let result = Blocks::configure_from(&cli, &c);
+        assert_eq!(result, Blocks(vec![Block::Etag]));
+
+        let argv = ["lsd", "--long", "memory:///logs/", "."];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let result = Blocks::configure_from(&cli, &Config::with_none());
+        assert_eq!(result, Blocks::long());
+
+        let argv = ["lsd", "--long", "--blocks", "user,name", "memory:///logs/"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let result = Blocks::configure_from(&cli, &Config::with_none());
+        assert_eq!(result, Blocks(vec![Block::User, Block::Name]));
+    }
+
     #[test]
     fn test_configure_from_with_blocks_and_without_long() {
         let argv = ["lsd", "--blocks", "permission"];
@@ -304,6 +406,23 @@ mod test_blocks {
         assert_eq!(Blocks::from_cli(&cli), Some(test_blocks));
     }
 
+    #[test]
+    fn test_from_cli_metadata() {
+        let argv = [
+            "lsd",
+            "--blocks",
+            "content-type,etag,content-md5,content-disposition",
+        ];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let test_blocks = Blocks(vec![
+            Block::ContentType,
+            Block::Etag,
+            Block::ContentMd5,
+            Block::ContentDisposition,
+        ]);
+        assert_eq!(Blocks::from_cli(&cli), Some(test_blocks));
+    }
+
     #[test]
     fn test_from_config_none() {
         assert_eq!(None, Blocks::from_config(&Config::with_none()));
@@ -358,43 +477,9 @@ mod test_blocks {
     }
 
     #[test]
-    fn test_context_not_present_on_cli() {
-        let argv = ["lsd", "--long"];
-        let cli = Cli::try_parse_from(argv).unwrap();
-        let parsed_blocks = Blocks::configure_from(&cli, &Config::with_none());
-        let it = parsed_blocks.0.iter();
-        assert_eq!(it.filter(|&x| *x == Block::Context).count(), 0);
-    }
-
-    #[test]
-    fn test_context_present_if_context_on() {
-        let argv = ["lsd", "--context"];
-        let cli = Cli::try_parse_from(argv).unwrap();
-        let parsed_blocks = Blocks::configure_from(&cli, &Config::with_none());
-        let it = parsed_blocks.0.iter();
-        assert_eq!(it.filter(|&x| *x == Block::Context).count(), 1);
-    }
-
-    #[test]
-    fn test_only_one_context_no_other_blocks_affected() {
-        let argv = [
-            "lsd",
-            "--context",
-            "--blocks",
-            "name,date,size,context,group,user,permission",
-        ];
-        let cli = Cli::try_parse_from(argv).unwrap();
-        let test_blocks = Blocks(vec![
-            Block::Name,
-            Block::Date,
-            Block::Size,
-            Block::Context,
-            Block::Group,
-            Block::User,
-            Block::Permission,
-        ]);
-        let parsed_blocks = Blocks::from_cli(&cli).unwrap();
-        assert_eq!(test_blocks, parsed_blocks);
+    fn test_context_rejected_on_cli() {
+        let argv = ["lsd", "--blocks", "name,context"];
+        assert!(Cli::try_parse_from(argv).is_err());
     }
 }
 
@@ -457,11 +542,6 @@ mod test_block {
         assert_eq!(Ok(Block::Links), Block::try_from("links"));
     }
 
-    #[test]
-    fn test_context() {
-        assert_eq!(Ok(Block::Context), Block::try_from("context"));
-    }
-
     #[test]
     fn test_block_headers() {
         assert_eq!(Block::INode.get_header(), "INode");
@@ -469,7 +549,6 @@ mod test_block {
         assert_eq!(Block::Permission.get_header(), "Permissions");
         assert_eq!(Block::User.get_header(), "User");
         assert_eq!(Block::Group.get_header(), "Group");
-        assert_eq!(Block::Context.get_header(), "Context");
         assert_eq!(Block::Size.get_header(), "Size");
         assert_eq!(Block::SizeValue.get_header(), "SizeValue");
         assert_eq!(Block::Date.get_header(), "Date Modified");
@@ -0,0 +1,104 @@
+//! This module defines the [Concurrency] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+use crate::print_error;
+
+/// The flag showing how many requests can be sent to the service at the same time.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub struct Concurrency(pub usize);
+
+impl Configurable<Self> for Concurrency {
+    /// Get a potential `Concurrency` value from [Cli].
+    ///
+    /// If the "jobs" argument is passed, this returns a `Concurrency` with its value in a [Some].
+    /// Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        cli.jobs.map(Self)
+    }
+
+    /// Get a potential `Concurrency` value from a [Config].
+    ///
+    /// If the `Config::concurrency` has a value greater than zero,
+    /// this returns it as the value of the `Concurrency`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        match config.concurrency {
+            Some(0) => {
+                print_error!("Not a valid concurrency: 0.");
+                None
+            }
+            value => value.map(Self),
+        }
+    }
+}
+
+/// The default value for `Concurrency` is 16.
+impl Default for Concurrency {
+    fn default() -> Self {
+        Self(16)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::Concurrency;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, Concurrency::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_jobs() {
+        let argv = ["lsd", "--jobs", "4"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(Concurrency(4)), Concurrency::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_zero_jobs() {
+        let argv = ["lsd", "--jobs", "0"];
+        assert!(Cli::try_parse_from(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, Concurrency::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_value() {
+        let mut c = Config::with_none();
+        c.concurrency = Some(32);
+        assert_eq!(Some(Concurrency(32)), Concurrency::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_zero() {
+        let mut c = Config::with_none();
+        c.concurrency = Some(0);
+        assert_eq!(None, Concurrency::from_config(&c));
+    }
+
+    #[test]
+    fn test_configure_from_default() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(
+            Concurrency(16),
+            Concurrency::configure_from(&cli, &Config::with_none())
+        );
+    }
+}
@@ -0,0 +1,229 @@
+//! This module defines the [Filters] options. To set it up from [Cli], a [Config] and its
+//! [Default] value, use the [configure_from](Filters::configure_from) method.
+
+use crate::app::Cli;
+use crate::config_file::Config;
+use crate::meta::Size;
+
+use chrono::{DateTime, Duration, Local, NaiveDate, TimeZone};
+use clap::error::ErrorKind;
+use clap::Error;
+
+/// The size and modification time ranges the listed files have to be in to be displayed.
+///
+/// The directories are always kept, so that their content can still be listed.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct Filters {
+    /// Only the files larger than this number of bytes are displayed.
+    pub larger: Option<u64>,
+    /// Only the files smaller than this number of bytes are displayed.
+    pub smaller: Option<u64>,
+    /// Only the files modified after this time are displayed.
+    pub newer: Option<DateTime<Local>>,
+    /// Only the files modified before this time are displayed.
+    pub older: Option<DateTime<Local>>,
+}
+
+impl Filters {
+    /// Get the Filters from either [Cli], a [Config] or the [Default] value.
+    ///
+    /// Each of the filters is taken from [Cli] if passed, else from [Config.filters].
+    ///
+    /// # Errors
+    ///
+    /// If a size or a date can not be parsed.
+    pub fn configure_from(cli: &Cli, config: &Config) -> Result<Self, Error> {
+        let config = config.filters.as_ref();
+        let larger = cli
+            .larger
+            .as_ref()
+            .or(config.and_then(|c| c.larger.as_ref()));
+        let smaller = cli
+            .smaller
+            .as_ref()
+            .or(config.and_then(|c| c.smaller.as_ref()));
+        let newer = cli.newer.as_ref().or(config.and_then(|c| c.newer.as_ref()));
+        let older = cli.older.as_ref().or(config.and_then(|c| c.older.as_ref()));
+
+        let size = |value: &String| Size::parse(value).map(|size| size.get_bytes());
+        Ok(Self {
+            larger: larger.map(size).transpose().map_err(invalid)?,
+            smaller: smaller.map(size).transpose().map_err(invalid)?,
+            newer: newer.map(|v| parse_time(v)).transpose().map_err(invalid)?,
+            older: older.map(|v| parse_time(v)).transpose().map_err(invalid)?,
+        })
+    }
+
+    /// Whether any of the size filters is set.
+    pub fn by_size(&self) -> bool {
+        self.larger.is_some() || self.smaller.is_some()
+    }
+
+    /// Whether any of the modification time filters is set.
+    pub fn by_time(&self) -> bool {
+        self.newer.is_some() || self.older.is_some()
+    }
+
+    /// Whether a file of `bytes` modified at `modified` is in the ranges. A file missing a value
+    /// a filter is set for is not.
+    pub fn matches(&self, bytes: Option<u64>, modified: Option<DateTime<Local>>) -> bool {
+        in_range(self.larger, bytes, |b, limit| b > limit)
+            && in_range(self.smaller, bytes, |b, limit| b < limit)
+            && in_range(self.newer, modified, |m, limit| m > limit)
+            && in_range(self.older, modified, |m, limit| m < limit)
+    }
+}
+
+/// Whether `value` is kept by the filter at `limit`, if any.
+fn in_range<T>(limit: Option<T>, value: Option<T>, keep: fn(T, T) -> bool) -> bool {
+    match limit {
+        Some(limit) => value.is_some_and(|value| keep(value, limit)),
+        None => true,
+    }
+}
+
+fn invalid(message: String) -> Error {
+    Error::raw(ErrorKind::ValueValidation, format!("{message}\n"))
+}
+
+/// Parse a point in time, either a date like the ones displayed, e.g. `2026-01-01` at midnight in
+/// the local time zone or a RFC 3339 date time, or how long ago it was, like the relative dates
+/// are displayed, e.g. `3 days ago`, `3 days` or `3d`.
+pub fn parse_time(value: &str) -> Result<DateTime<Local>, String> {
+    let value = value.trim();
+    if let Ok(date_time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(date_time.with_timezone(&Local));
+    }
+    if let Some(midnight) = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|day| day.and_hms_opt(0, 0, 0))
+    {
+        return Local
+            .from_local_datetime(&midnight)
+            .earliest()
+            .ok_or_else(|| format!("invalid date {value} in the local time zone"));
+    }
+    let ago = parse_duration(value).ok_or_else(|| {
+        format!(
+            "invalid date {value}, expected YYYY-MM-DD, a RFC 3339 date time or a duration like \
+             3d"
+        )
+    })?;
+    Local::now()
+        .checked_sub_signed(ago)
+        .ok_or_else(|| format!("invalid date {value}, too far in the past"))
+}
+
+/// Parse a duration like `90s`, `3 days ago` or `1y`, saturating at the longest [Duration].
+fn parse_duration(value: &str) -> Option<Duration> {
+    const DAY: i64 = 24 * 60 * 60;
+
+    let value = value.strip_suffix("ago").unwrap_or(value).trim_end();
+    let split = value.find(|c: char| !c.is_ascii_digit())?;
+    let (number, unit) = value.split_at(split);
+    let number: i64 = number.parse().ok()?;
+    let unit = match unit.trim() {
+        "s" | "sec" | "second" | "seconds" => 1,
+        "m" | "min" | "minute" | "minutes" => 60,
+        "h" | "hour" | "hours" => 60 * 60,
+        "d" | "day" | "days" => DAY,
+        "w" | "week" | "weeks" => 7 * DAY,
+        "mo" | "month" | "months" => 30 * DAY,
+        "y" | "year" | "years" => 365 * DAY,
+        _ => return None,
+    };
+    // chrono has no checked constructors, and `Duration::seconds` panics out of its range.
+    let seconds = number
+        .saturating_mul(unit)
+        .min(Duration::max_value().num_seconds());
+    Some(Duration::seconds(seconds))
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::{parse_time, Filters};
+
+    use crate::app::Cli;
+    use crate::config_file::{self, Config};
+
+    use chrono::{DateTime, Duration, Local, NaiveDate, TimeZone};
+
+    fn new_year() -> Option<DateTime<Local>> {
+        let midnight = NaiveDate::from_ymd(2026, 1, 1).and_hms(0, 0, 0);
+        Local.from_local_datetime(&midnight).earliest()
+    }
+
+    #[test]
+    fn test_configure_from_default() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(
+            Filters::default(),
+            Filters::configure_from(&cli, &Config::with_none()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_configure_from_cli_over_config() {
+        let argv = ["lsd", "--larger", "1KB", "--older", "2026-01-01"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let mut c = Config::with_none();
+        c.filters = Some(config_file::Filters {
+            larger: Some("1M".into()),
+            smaller: Some("2M".into()),
+            newer: None,
+            older: None,
+        });
+
+        let filters = Filters::configure_from(&cli, &c).unwrap();
+        assert_eq!(Some(1024), filters.larger);
+        assert_eq!(Some(2 * 1024 * 1024), filters.smaller);
+        assert_eq!(None, filters.newer);
+        assert_eq!(new_year(), filters.older);
+    }
+
+    #[test]
+    fn test_configure_from_invalid() {
+        let argv = ["lsd", "--smaller", "12 parsecs"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert!(Filters::configure_from(&cli, &Config::with_none()).is_err());
+    }
+
+    #[test]
+    fn test_parse_time() {
+        assert_eq!(new_year(), parse_time("2026-01-01").ok());
+        let time = parse_time("3 days ago").unwrap();
+        let ago = Local::now() - time;
+        assert!(ago >= Duration::days(3) && ago < Duration::days(3) + Duration::minutes(1));
+        let time = parse_time("2h").unwrap();
+        let ago = Local::now() - time;
+        assert!(ago >= Duration::hours(2) && ago < Duration::hours(2) + Duration::minutes(1));
+        assert!(parse_time("yesterday").is_err());
+    }
+
+    #[test]
+    fn test_parse_time_out_of_range() {
+        assert!(parse_time("99999999999999d").is_err());
+        assert!(parse_time("999999y").is_err());
+        assert!(parse_time("99999999999999999999s").is_err());
+
+        let argv = ["lsd", "--newer", "99999999999999d"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert!(Filters::configure_from(&cli, &Config::with_none()).is_err());
+    }
+
+    #[test]
+    fn test_matches() {
+        let filters = Filters {
+            larger: Some(10),
+            newer: parse_time("1d").ok(),
+            ..Filters::default()
+        };
+        assert!(filters.matches(Some(11), Some(Local::now())));
+        assert!(!filters.matches(Some(10), Some(Local::now())));
+        assert!(!filters.matches(Some(11), parse_time("2d").ok()));
+        assert!(!filters.matches(None, Some(Local::now())));
+    }
+}
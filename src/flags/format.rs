@@ -0,0 +1,126 @@
+//! This module defines the [Format] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+use serde::Deserialize;
+
+/// The flag showing whether to print the entries for humans or for other programs.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum Format {
+    /// The variant to print the grid or the tree with colors and icons.
+    #[default]
+    Text,
+    /// The variant to print one JSON array of all the entries.
+    Json,
+    /// The variant to print one JSON object per line, as soon as the entry is listed.
+    Ndjson,
+    /// The variant to print comma separated values, with a header row.
+    Csv,
+    /// The variant to print tab separated values, with a header row.
+    Tsv,
+}
+
+impl Format {
+    fn from_arg_str(value: &str) -> Self {
+        match value {
+            "text" => Self::Text,
+            "json" => Self::Json,
+            "ndjson" => Self::Ndjson,
+            "csv" => Self::Csv,
+            "tsv" => Self::Tsv,
+            // Invalid value should be handled by `clap` when building an `Cli`
+            other => unreachable!("Invalid value '{other}' for 'format'"),
+        }
+    }
+}
+
+impl Configurable<Self> for Format {
+    /// Get a potential `Format` variant from [Cli].
+    ///
+    /// If the "format" argument is passed, the corresponding `Format` variant is returned in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        cli.format.as_deref().map(Self::from_arg_str)
+    }
+
+    /// Get a potential `Format` variant from a [Config].
+    ///
+    /// If the `Config::format` has value and is one of "text", "json", "ndjson", "csv" or "tsv",
+    /// this returns the corresponding `Format` variant in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.format
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::Format;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, Format::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_json() {
+        let argv = ["lsd", "--format", "json"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(Format::Json), Format::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_ndjson() {
+        let argv = ["lsd", "--format", "ndjson"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(Format::Ndjson), Format::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_tsv() {
+        let argv = ["lsd", "--format", "tsv"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(Format::Tsv), Format::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_invalid() {
+        let argv = ["lsd", "--format", "xml"];
+        assert!(Cli::try_parse_from(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, Format::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_csv() {
+        let mut c = Config::with_none();
+        c.format = Some(Format::Csv);
+        assert_eq!(Some(Format::Csv), Format::from_config(&c));
+    }
+
+    #[test]
+    fn test_configure_from_default() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(
+            Format::Text,
+            Format::configure_from(&cli, &Config::with_none())
+        );
+    }
+}
@@ -0,0 +1,47 @@
+//! This module defines the [HideDotKeys] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+/// The flag showing whether the keys under a `.`-prefixed segment are hidden on object stores,
+/// like the dotfiles are on the local filesystem.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct HideDotKeys(pub bool);
+
+impl Configurable<Self> for HideDotKeys {
+    /// The `HideDotKeys` flag can only be set in the config file, this always returns [None].
+    fn from_cli(_: &Cli) -> Option<Self> {
+        None
+    }
+
+    /// Get a potential `HideDotKeys` value from a [Config].
+    ///
+    /// If the `Config::hide_dot_keys` has value, this returns it as the value of the
+    /// `HideDotKeys`, in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.hide_dot_keys.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::HideDotKeys;
+
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, HideDotKeys::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.hide_dot_keys = Some(true);
+        assert_eq!(Some(HideDotKeys(true)), HideDotKeys::from_config(&c));
+    }
+}
@@ -44,18 +44,7 @@ impl IgnoreGlobs {
             return None;
         }
 
-        let mut glob_set_builder = GlobSetBuilder::new();
-
-        for value in &cli.ignore_glob {
-            match Self::create_glob(value) {
-                Ok(glob) => {
-                    glob_set_builder.add(glob);
-                }
-                Err(err) => return Some(Err(err)),
-            }
-        }
-
-        Some(Self::create_glob_set(&glob_set_builder).map(Self))
+        Some(Self::from_patterns(&cli.ignore_glob))
     }
 
     /// Get a potential [IgnoreGlobs] from a [Config].
@@ -67,18 +56,22 @@ impl IgnoreGlobs {
     /// not contain such a key, this returns [None].
     fn from_config(config: &Config) -> Option<Result<Self, Error>> {
         let globs = config.ignore_globs.as_ref()?;
+        Some(Self::from_patterns(globs))
+    }
+
+    /// Build an [IgnoreGlobs] matching any of the `patterns`.
+    ///
+    /// # Errors
+    ///
+    /// If either of the [Glob::new] or [GlobSetBuilder.build] methods return an [Err].
+    pub fn from_patterns(patterns: &[String]) -> Result<Self, Error> {
         let mut glob_set_builder = GlobSetBuilder::new();
 
-        for glob in globs {
-            match Self::create_glob(glob) {
-                Ok(glob) => {
-                    glob_set_builder.add(glob);
-                }
-                Err(err) => return Some(Err(err)),
-            }
+        for pattern in patterns {
+            glob_set_builder.add(Self::create_glob(pattern)?);
         }
 
-        Some(Self::create_glob_set(&glob_set_builder).map(Self))
+        Self::create_glob_set(&glob_set_builder).map(Self)
     }
 
     /// Create a [Glob] from a provided pattern.
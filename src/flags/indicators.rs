@@ -0,0 +1,83 @@
+//! This module defines the [Indicators] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+/// The flag showing whether to append indicator characters to file names, like `/` to directories.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct Indicators(pub bool);
+
+impl Configurable<Self> for Indicators {
+    /// Get a potential `Indicators` value from [Cli].
+    ///
+    /// If the "classify" argument is passed, this returns a `Indicators` with value `true` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        if cli.indicators {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `Indicators` value from a [Config].
+    ///
+    /// If the `Config::classic` is `true`, this returns a `Indicators` with value `false` in a
+    /// [Some]. Otherwise if the `Config::indicators` has value, this returns it as the value of
+    /// the `Indicators`, in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if config.classic == Some(true) {
+            Some(Self(false))
+        } else {
+            config.indicators.map(Self)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::Indicators;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, Indicators::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_true() {
+        let argv = ["lsd", "--classify"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(Indicators(true)), Indicators::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, Indicators::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.indicators = Some(true);
+        assert_eq!(Some(Indicators(true)), Indicators::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_classic_mode() {
+        let mut c = Config::with_none();
+        c.classic = Some(true);
+        c.indicators = Some(true);
+        assert_eq!(Some(Indicators(false)), Indicators::from_config(&c));
+    }
+}
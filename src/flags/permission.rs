@@ -0,0 +1,98 @@
+//! This module defines the [PermissionFlag]. To set it up from [Cli], a [Config] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+use serde::Deserialize;
+
+/// The flag showing how to display the permissions of local files.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum PermissionFlag {
+    /// The variant to show the permissions as `rwx` triplets, like `ls -l`.
+    #[default]
+    Rwx,
+    /// The variant to show the permissions as an octal number, like `chmod`.
+    Octal,
+}
+
+impl PermissionFlag {
+    fn from_arg_str(value: &str) -> Self {
+        match value {
+            "rwx" => Self::Rwx,
+            "octal" => Self::Octal,
+            // Invalid value should be handled by `clap` when building an `Cli`
+            other => unreachable!("Invalid value '{other}' for 'permission'"),
+        }
+    }
+}
+
+impl Configurable<Self> for PermissionFlag {
+    /// Get a potential `PermissionFlag` variant from [Cli].
+    ///
+    /// If any of the "rwx" or "octal" arguments is passed, the corresponding `PermissionFlag`
+    /// variant is returned in a [Some]. If neither of them is passed, this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        cli.permission.as_deref().map(Self::from_arg_str)
+    }
+
+    /// Get a potential `PermissionFlag` variant from a [Config].
+    ///
+    /// If the `Config::permission` has value and is one of "rwx" or "octal", this returns the
+    /// corresponding `PermissionFlag` variant in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.permission
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::PermissionFlag;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_default() {
+        assert_eq!(PermissionFlag::Rwx, PermissionFlag::default());
+    }
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, PermissionFlag::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_octal() {
+        let argv = ["lsd", "--permission", "octal"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(PermissionFlag::Octal), PermissionFlag::from_cli(&cli));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_cli_unknown() {
+        let argv = ["lsd", "--permission", "unknown"];
+        let _ = Cli::try_parse_from(argv).unwrap();
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, PermissionFlag::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_octal() {
+        let mut c = Config::with_none();
+        c.permission = Some(PermissionFlag::Octal);
+        assert_eq!(Some(PermissionFlag::Octal), PermissionFlag::from_config(&c));
+    }
+}
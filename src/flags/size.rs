@@ -22,7 +22,7 @@ pub enum SizeFlag {
 }
 
 impl SizeFlag {
-    fn from_arg_str(value: &str) -> Self {
+    pub fn from_arg_str(value: &str) -> Self {
         match value {
             "default" => Self::Default,
             "short" => Self::Short,
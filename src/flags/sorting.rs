@@ -5,13 +5,14 @@ use super::Configurable;
 
 use crate::app::Cli;
 use crate::config_file::Config;
+use crate::print_error;
 
 use serde::Deserialize;
 
 /// A collection of flags on how to sort the output.
-#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
 pub struct Sorting {
-    pub column: SortColumn,
+    pub columns: SortColumns,
     pub order: SortOrder,
     pub dir_grouping: DirGrouping,
 }
@@ -19,63 +20,149 @@ pub struct Sorting {
 impl Sorting {
     /// Get a `Sorting` struct from [Cli], a [Config] or the [Default] values.
     ///
-    /// The [SortColumn], [SortOrder] and [DirGrouping] are configured with their respective
+    /// The [SortColumns], [SortOrder] and [DirGrouping] are configured with their respective
     /// [Configurable] implementation.
     pub fn configure_from(cli: &Cli, config: &Config) -> Self {
-        let column = SortColumn::configure_from(cli, config);
+        let columns = SortColumns::configure_from(cli, config);
         let order = SortOrder::configure_from(cli, config);
         let dir_grouping = DirGrouping::configure_from(cli, config);
         Self {
-            column,
+            columns,
             order,
             dir_grouping,
         }
     }
 }
 
-/// The flag showing which column to use for sorting.
-#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize, Default)]
-#[serde(rename_all = "kebab-case")]
+/// A column to sort by.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
 pub enum SortColumn {
-    None,
     Extension,
-    #[default]
     Name,
     Time,
     Size,
     Version,
+    ContentType,
+    Etag,
+    Path,
+}
+
+impl SortColumn {
+    /// Get a value from a column name, like the ones accepted by `--sort`.
+    fn from_arg_str(value: &str) -> Option<Self> {
+        match value {
+            "extension" => Some(Self::Extension),
+            "name" => Some(Self::Name),
+            "time" => Some(Self::Time),
+            "size" => Some(Self::Size),
+            "version" => Some(Self::Version),
+            "content-type" => Some(Self::ContentType),
+            "etag" => Some(Self::Etag),
+            "path" => Some(Self::Path),
+            _ => None,
+        }
+    }
+}
+
+/// A column to sort by, in its own direction.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub struct SortKey {
+    pub column: SortColumn,
+    pub order: SortOrder,
+}
+
+impl From<SortColumn> for SortKey {
+    fn from(column: SortColumn) -> Self {
+        Self {
+            column,
+            order: SortOrder::Default,
+        }
+    }
+}
+
+/// The flag showing which columns to sort by, each of the next keys only ordering the entries
+/// the previous ones left equal. No key at all leaves the entries in the listing order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SortColumns(pub Vec<SortKey>);
+
+impl Default for SortColumns {
+    fn default() -> Self {
+        Self(vec![SortColumn::Name.into()])
+    }
+}
+
+impl SortColumns {
+    /// Get a value from comma separated column names, like `content-type,-time`. A name prefixed
+    /// with a `-` sorts in the reverse direction, and `none` does not sort.
+    fn from_str(value: &str) -> Option<Self> {
+        let mut keys = vec![];
+        for key in value.split(',').map(str::trim) {
+            let (name, order) = match key.strip_prefix('-') {
+                Some(name) => (name, SortOrder::Reverse),
+                None => (key, SortOrder::Default),
+            };
+            if name == "none" {
+                continue;
+            }
+            match SortColumn::from_arg_str(name) {
+                Some(column) => keys.push(SortKey { column, order }),
+                None => {
+                    print_error!("Not a valid sort column: {}.", name);
+                    return None;
+                }
+            }
+        }
+        Some(Self(keys))
+    }
+
+    /// Whether the entries are sorted by `column`, in any direction.
+    pub fn contains(&self, column: SortColumn) -> bool {
+        self.0.iter().any(|key| key.column == column)
+    }
+
+    /// Whether the entries are left in the listing order.
+    pub fn is_none(&self) -> bool {
+        self.0.is_empty()
+    }
 }
 
-impl Configurable<Self> for SortColumn {
-    /// Get a potential `SortColumn` variant from [Cli].
+impl Configurable<Self> for SortColumns {
+    /// Get potential `SortColumns` from [Cli].
     ///
-    /// If either the "timesort" or "sizesort" arguments are passed, this returns the corresponding
-    /// `SortColumn` variant in a [Some]. Otherwise this returns [None].
+    /// If the "sort" argument is passed, this returns its columns in a [Some]. Otherwise if one
+    /// of the single column arguments, like "timesort" or "sizesort", is passed, this returns the
+    /// corresponding column in a [Some]. Otherwise this returns [None].
     fn from_cli(cli: &Cli) -> Option<Self> {
-        let sort = cli.sort.as_deref();
-
-        if cli.timesort || sort == Some("time") {
-            Some(Self::Time)
-        } else if cli.sizesort || sort == Some("size") {
-            Some(Self::Size)
-        } else if cli.extensionsort || sort == Some("extension") {
-            Some(Self::Extension)
-        } else if cli.versionsort || sort == Some("version") {
-            Some(Self::Version)
-        } else if cli.no_sort || sort == Some("none") {
-            Some(Self::None)
-        } else {
-            None
+        if !cli.sort.is_empty() {
+            return Self::from_str(&cli.sort.join(","));
         }
+
+        let column = if cli.timesort {
+            SortColumn::Time
+        } else if cli.sizesort {
+            SortColumn::Size
+        } else if cli.extensionsort {
+            SortColumn::Extension
+        } else if cli.versionsort {
+            SortColumn::Version
+        } else if cli.no_sort {
+            return Some(Self(vec![]));
+        } else {
+            return None;
+        };
+        Some(Self(vec![column.into()]))
     }
 
-    /// Get a potential `SortColumn` variant from a [Config].
+    /// Get potential `SortColumns` from a [Config].
     ///
-    /// If the `Config::sorting::column` has value and is one of "time", "size" or "name",
-    /// this returns the corresponding variant in a [Some].
-    /// Otherwise this returns [None].
+    /// If the `Config::sorting::column` has value and is made of valid column names, this
+    /// returns the corresponding columns in a [Some]. Otherwise this returns [None].
     fn from_config(config: &Config) -> Option<Self> {
-        config.sorting.as_ref().and_then(|s| s.column)
+        config
+            .sorting
+            .as_ref()
+            .and_then(|s| s.column.as_deref())
+            .and_then(Self::from_str)
     }
 }
 
@@ -87,6 +174,16 @@ pub enum SortOrder {
     Reverse,
 }
 
+impl SortOrder {
+    /// The other direction.
+    pub fn reverse(self) -> Self {
+        match self {
+            Self::Default => Self::Reverse,
+            Self::Reverse => Self::Default,
+        }
+    }
+}
+
 impl Configurable<Self> for SortOrder {
     /// Get a potential `SortOrder` variant from [Cli].
     ///
@@ -175,97 +272,135 @@ impl Configurable<Self> for DirGrouping {
 }
 
 #[cfg(test)]
-mod test_sort_column {
+mod test_sort_columns {
     use clap::Parser;
 
-    use super::SortColumn;
+    use super::{SortColumn, SortColumns, SortKey, SortOrder};
 
     use crate::app::Cli;
     use crate::config_file::{Config, Sorting};
     use crate::flags::Configurable;
 
+    fn by(column: SortColumn) -> Option<SortColumns> {
+        Some(SortColumns(vec![column.into()]))
+    }
+
     #[test]
     fn test_from_cli_none() {
         let argv = ["lsd"];
         let cli = Cli::try_parse_from(argv).unwrap();
-        assert_eq!(None, SortColumn::from_cli(&cli));
+        assert_eq!(None, SortColumns::from_cli(&cli));
     }
 
     #[test]
     fn test_from_cli_extension() {
         let argv = ["lsd", "--extensionsort"];
         let cli = Cli::try_parse_from(argv).unwrap();
-        assert_eq!(Some(SortColumn::Extension), SortColumn::from_cli(&cli));
+        assert_eq!(by(SortColumn::Extension), SortColumns::from_cli(&cli));
     }
 
     #[test]
     fn test_from_cli_time() {
         let argv = ["lsd", "--timesort"];
         let cli = Cli::try_parse_from(argv).unwrap();
-        assert_eq!(Some(SortColumn::Time), SortColumn::from_cli(&cli));
+        assert_eq!(by(SortColumn::Time), SortColumns::from_cli(&cli));
     }
 
     #[test]
     fn test_from_cli_size() {
         let argv = ["lsd", "--sizesort"];
         let cli = Cli::try_parse_from(argv).unwrap();
-        assert_eq!(Some(SortColumn::Size), SortColumn::from_cli(&cli));
+        assert_eq!(by(SortColumn::Size), SortColumns::from_cli(&cli));
     }
 
     #[test]
     fn test_from_cli_version() {
         let argv = ["lsd", "--versionsort"];
         let cli = Cli::try_parse_from(argv).unwrap();
-        assert_eq!(Some(SortColumn::Version), SortColumn::from_cli(&cli));
+        assert_eq!(by(SortColumn::Version), SortColumns::from_cli(&cli));
     }
 
     #[test]
     fn test_from_cli_no_sort() {
         let argv = ["lsd", "--no-sort"];
         let cli = Cli::try_parse_from(argv).unwrap();
-        assert_eq!(Some(SortColumn::None), SortColumn::from_cli(&cli));
+        assert_eq!(Some(SortColumns(vec![])), SortColumns::from_cli(&cli));
     }
 
     #[test]
     fn test_from_cli_sort() {
         let argv = ["lsd", "--sort", "time"];
         let cli = Cli::try_parse_from(argv).unwrap();
-        assert_eq!(Some(SortColumn::Time), SortColumn::from_cli(&cli));
+        assert_eq!(by(SortColumn::Time), SortColumns::from_cli(&cli));
 
         let argv = ["lsd", "--sort", "size"];
         let cli = Cli::try_parse_from(argv).unwrap();
-        assert_eq!(Some(SortColumn::Size), SortColumn::from_cli(&cli));
+        assert_eq!(by(SortColumn::Size), SortColumns::from_cli(&cli));
 
         let argv = ["lsd", "--sort", "extension"];
         let cli = Cli::try_parse_from(argv).unwrap();
-        assert_eq!(Some(SortColumn::Extension), SortColumn::from_cli(&cli));
+        assert_eq!(by(SortColumn::Extension), SortColumns::from_cli(&cli));
 
         let argv = ["lsd", "--sort", "version"];
         let cli = Cli::try_parse_from(argv).unwrap();
-        assert_eq!(Some(SortColumn::Version), SortColumn::from_cli(&cli));
+        assert_eq!(by(SortColumn::Version), SortColumns::from_cli(&cli));
+
+        let argv = ["lsd", "--sort", "content-type"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(by(SortColumn::ContentType), SortColumns::from_cli(&cli));
+
+        let argv = ["lsd", "--sort", "etag"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(by(SortColumn::Etag), SortColumns::from_cli(&cli));
+
+        let argv = ["lsd", "--sort", "path"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(by(SortColumn::Path), SortColumns::from_cli(&cli));
 
         let argv = ["lsd", "--sort", "none"];
         let cli = Cli::try_parse_from(argv).unwrap();
-        assert_eq!(Some(SortColumn::None), SortColumn::from_cli(&cli));
+        assert_eq!(Some(SortColumns(vec![])), SortColumns::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_sort_keys() {
+        let argv = ["lsd", "--sort", "-size,name"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(
+            Some(SortColumns(vec![
+                SortKey {
+                    column: SortColumn::Size,
+                    order: SortOrder::Reverse,
+                },
+                SortColumn::Name.into(),
+            ])),
+            SortColumns::from_cli(&cli)
+        );
+    }
+
+    #[test]
+    fn test_from_cli_sort_invalid() {
+        let argv = ["lsd", "--sort", "size,color"];
+        assert!(Cli::try_parse_from(argv).is_err());
     }
 
     #[test]
     fn test_multi_sort() {
         let argv = ["lsd", "--sort", "size", "--sort", "time"];
         let cli = Cli::try_parse_from(argv).unwrap();
-        assert_eq!(Some(SortColumn::Time), SortColumn::from_cli(&cli));
+        assert_eq!(by(SortColumn::Time), SortColumns::from_cli(&cli));
     }
 
     #[test]
     fn test_multi_sort_use_last() {
         let argv = ["lsd", "--sort", "size", "-t", "-S", "-X", "--sort", "time"];
         let cli = Cli::try_parse_from(argv).unwrap();
-        assert_eq!(Some(SortColumn::Time), SortColumn::from_cli(&cli));
+        assert_eq!(by(SortColumn::Time), SortColumns::from_cli(&cli));
     }
 
     #[test]
     fn test_from_config_empty() {
-        assert_eq!(None, SortColumn::from_config(&Config::with_none()));
+        assert_eq!(None, SortColumns::from_config(&Config::with_none()));
     }
 
     #[test]
@@ -277,62 +412,49 @@ mod test_sort_column {
             dir_grouping: None,
         });
 
-        assert_eq!(None, SortColumn::from_config(&c));
-    }
-
-    #[test]
-    fn test_from_config_extension() {
-        let mut c = Config::with_none();
-        c.sorting = Some(Sorting {
-            column: Some(SortColumn::Extension),
-            reverse: None,
-            dir_grouping: None,
-        });
-        assert_eq!(Some(SortColumn::Extension), SortColumn::from_config(&c));
+        assert_eq!(None, SortColumns::from_config(&c));
     }
 
     #[test]
     fn test_from_config_name() {
         let mut c = Config::with_none();
         c.sorting = Some(Sorting {
-            column: Some(SortColumn::Name),
+            column: Some("name".into()),
             reverse: None,
             dir_grouping: None,
         });
-        assert_eq!(Some(SortColumn::Name), SortColumn::from_config(&c));
+        assert_eq!(by(SortColumn::Name), SortColumns::from_config(&c));
     }
 
     #[test]
-    fn test_from_config_time() {
+    fn test_from_config_keys() {
         let mut c = Config::with_none();
         c.sorting = Some(Sorting {
-            column: Some(SortColumn::Time),
+            column: Some("content-type,-time".into()),
             reverse: None,
             dir_grouping: None,
         });
-        assert_eq!(Some(SortColumn::Time), SortColumn::from_config(&c));
-    }
-
-    #[test]
-    fn test_from_config_size() {
-        let mut c = Config::with_none();
-        c.sorting = Some(Sorting {
-            column: Some(SortColumn::Size),
-            reverse: None,
-            dir_grouping: None,
-        });
-        assert_eq!(Some(SortColumn::Size), SortColumn::from_config(&c));
+        assert_eq!(
+            Some(SortColumns(vec![
+                SortColumn::ContentType.into(),
+                SortKey {
+                    column: SortColumn::Time,
+                    order: SortOrder::Reverse,
+                },
+            ])),
+            SortColumns::from_config(&c)
+        );
     }
 
     #[test]
-    fn test_from_config_version() {
+    fn test_from_config_invalid() {
         let mut c = Config::with_none();
         c.sorting = Some(Sorting {
-            column: Some(SortColumn::Version),
+            column: Some("size,color".into()),
             reverse: None,
             dir_grouping: None,
         });
-        assert_eq!(Some(SortColumn::Version), SortColumn::from_config(&c));
+        assert_eq!(None, SortColumns::from_config(&c));
     }
 }
 
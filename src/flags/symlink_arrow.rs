@@ -0,0 +1,77 @@
+//! This module defines the [SymlinkArrow]. To set it up from [Config] and its [Default] value,
+//! use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+use std::fmt;
+
+/// The string between the name of a symbolic link and its target.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SymlinkArrow(String);
+
+impl Configurable<Self> for SymlinkArrow {
+    /// There is no command line argument for the arrow, this always returns [None].
+    fn from_cli(_: &Cli) -> Option<Self> {
+        None
+    }
+
+    /// Get a potential `SymlinkArrow` value from a [Config].
+    ///
+    /// If the `Config::symlink-arrow` has value, this returns it as the value of the
+    /// `SymlinkArrow`, in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.symlink_arrow.clone().map(SymlinkArrow)
+    }
+}
+
+/// The default value for the `SymlinkArrow` is `\u{21d2}` (⇒).
+impl Default for SymlinkArrow {
+    fn default() -> Self {
+        Self(String::from("\u{21d2}"))
+    }
+}
+
+impl fmt::Display for SymlinkArrow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SymlinkArrow;
+
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_symlink_arrow_from_config_utf8() {
+        let mut c = Config::with_none();
+        c.symlink_arrow = Some("↹".into());
+        assert_eq!(
+            Some(SymlinkArrow(String::from("\u{21B9}"))),
+            SymlinkArrow::from_config(&c)
+        );
+    }
+
+    #[test]
+    fn test_symlink_arrow_from_config_none() {
+        assert_eq!(None, SymlinkArrow::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_symlink_arrow_default() {
+        assert_eq!(
+            SymlinkArrow(String::from("\u{21d2}")),
+            SymlinkArrow::default()
+        );
+    }
+
+    #[test]
+    fn test_symlink_display() {
+        assert_eq!("⇒", format!("{}", SymlinkArrow::default()));
+    }
+}
@@ -0,0 +1,70 @@
+//! This module defines the [NoSymlink] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+/// The flag showing whether to hide the targets of symbolic links.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct NoSymlink(pub bool);
+
+impl Configurable<Self> for NoSymlink {
+    /// Get a potential `NoSymlink` value from [Cli].
+    ///
+    /// If the "no-symlink" argument is passed, this returns a `NoSymlink` with value `true` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        if cli.no_symlink {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `NoSymlink` value from a [Config].
+    ///
+    /// If the `Config::no-symlink` has value, this returns it as the value of the `NoSymlink`, in
+    /// a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.no_symlink.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::NoSymlink;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, NoSymlink::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_true() {
+        let argv = ["lsd", "--no-symlink"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(NoSymlink(true)), NoSymlink::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, NoSymlink::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.no_symlink = Some(true);
+        assert_eq!(Some(NoSymlink(true)), NoSymlink::from_config(&c));
+    }
+}
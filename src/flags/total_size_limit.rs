@@ -0,0 +1,102 @@
+//! This module defines the [TotalSizeLimit] options. To set it up from [Cli], a [Config] and its
+//! [Default] value, use the [configure_from](TotalSizeLimit::configure_from) method.
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+use std::time::Duration;
+
+/// The options capping the walk done to compute the total size of directories.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct TotalSizeLimit {
+    /// How deep to walk under each directory, [None] for no limit.
+    pub depth: Option<usize>,
+    /// How long the walk of each input may take, [None] for no limit.
+    pub timeout: Option<Duration>,
+}
+
+impl TotalSizeLimit {
+    /// Get the TotalSizeLimit from either [Cli], a [Config] or the [Default] value.
+    ///
+    /// Each of the options is taken from [Cli] if passed, else from [Config.total_size_limit].
+    pub fn configure_from(cli: &Cli, config: &Config) -> Self {
+        let (config_depth, config_timeout) = match &config.total_size_limit {
+            Some(limit) => (limit.depth, limit.timeout),
+            None => (None, None),
+        };
+
+        Self {
+            depth: cli.total_size_depth.or(config_depth),
+            timeout: cli
+                .total_size_timeout
+                .or(config_timeout)
+                .map(Duration::from_secs),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::TotalSizeLimit;
+
+    use crate::app::Cli;
+    use crate::config_file::{self, Config};
+
+    use std::time::Duration;
+
+    #[test]
+    fn test_configure_from_default() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(
+            TotalSizeLimit::default(),
+            TotalSizeLimit::configure_from(&cli, &Config::with_none())
+        );
+    }
+
+    #[test]
+    fn test_configure_from_cli() {
+        let argv = [
+            "lsd",
+            "--total-size",
+            "--total-size-depth",
+            "3",
+            "--total-size-timeout",
+            "10",
+        ];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(
+            TotalSizeLimit {
+                depth: Some(3),
+                timeout: Some(Duration::from_secs(10)),
+            },
+            TotalSizeLimit::configure_from(&cli, &Config::with_none())
+        );
+    }
+
+    #[test]
+    fn test_from_cli_requires_total_size() {
+        let argv = ["lsd", "--total-size-depth", "3"];
+        assert!(Cli::try_parse_from(argv).is_err());
+    }
+
+    #[test]
+    fn test_configure_from_config() {
+        let argv = ["lsd", "--total-size-depth", "1", "--total-size"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let mut c = Config::with_none();
+        c.total_size_limit = Some(config_file::TotalSizeLimit {
+            depth: Some(5),
+            timeout: Some(30),
+        });
+        assert_eq!(
+            TotalSizeLimit {
+                depth: Some(1),
+                timeout: Some(Duration::from_secs(30)),
+            },
+            TotalSizeLimit::configure_from(&cli, &c)
+        );
+    }
+}
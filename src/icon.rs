@@ -47,11 +47,18 @@ impl Icons {
                     icon
                 } else {
                     match file_type {
+                        FileType::SymLink { is_dir: true } => &t.filetype.symlink_dir,
+                        FileType::SymLink { is_dir: false } => &t.filetype.symlink_file,
+                        FileType::Socket => &t.filetype.socket,
+                        FileType::Pipe => &t.filetype.pipe,
+                        FileType::CharDevice => &t.filetype.device_char,
+                        FileType::BlockDevice => &t.filetype.device_block,
+                        FileType::Special => &t.filetype.special,
                         FileType::Directory { .. } => &t.filetype.dir,
                         // If a file has no extension and is executable, show an icon.
                         // Except for Windows, it marks everything as an executable.
                         #[cfg(not(windows))]
-                        FileType::File => &t.filetype.executable,
+                        FileType::File { exec: true, .. } => &t.filetype.executable,
 
                         // allow, Windows will reach here
                         #[allow(unreachable_patterns)]
@@ -68,93 +75,93 @@ impl Icons {
 #[cfg(test)]
 mod test {
     use super::{IconTheme, Icons};
+    use crate::dal::Meta;
     use crate::flags::{IconOption, IconTheme as FlagTheme};
-    use crate::meta::Meta;
     use std::fs::File;
     use tempfile::tempdir;
 
-    #[test]
-    fn get_no_icon_never_tty() {
+    #[tokio::test]
+    async fn get_no_icon_never_tty() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
         let file_path = tmp_dir.path().join("file.txt");
         File::create(&file_path).expect("failed to create file");
-        let meta = Meta::from_path(&file_path, false).unwrap();
+        let meta = Meta::from_local(&file_path).await.unwrap();
 
         let icons = Icons::new(true, IconOption::Never, FlagTheme::Fancy, " ".to_string());
-        let icon = icons.get(&meta.name);
+        let icon = icons.get(&meta.name());
 
         assert_eq!(icon, "");
     }
-    #[test]
-    fn get_no_icon_never_not_tty() {
+    #[tokio::test]
+    async fn get_no_icon_never_not_tty() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
         let file_path = tmp_dir.path().join("file.txt");
         File::create(&file_path).expect("failed to create file");
-        let meta = Meta::from_path(&file_path, false).unwrap();
+        let meta = Meta::from_local(&file_path).await.unwrap();
 
         let icons = Icons::new(false, IconOption::Never, FlagTheme::Fancy, " ".to_string());
-        let icon = icons.get(&meta.name);
+        let icon = icons.get(&meta.name());
 
         assert_eq!(icon, "");
     }
 
-    #[test]
-    fn get_no_icon_auto() {
+    #[tokio::test]
+    async fn get_no_icon_auto() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
         let file_path = tmp_dir.path().join("file.txt");
         File::create(&file_path).expect("failed to create file");
-        let meta = Meta::from_path(&file_path, false).unwrap();
+        let meta = Meta::from_local(&file_path).await.unwrap();
 
         let icons = Icons::new(false, IconOption::Auto, FlagTheme::Fancy, " ".to_string());
-        let icon = icons.get(&meta.name);
+        let icon = icons.get(&meta.name());
 
         assert_eq!(icon, "");
     }
-    #[test]
-    fn get_icon_auto_tty() {
+    #[tokio::test]
+    async fn get_icon_auto_tty() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
         let file_path = tmp_dir.path().join("file.txt");
         File::create(&file_path).expect("failed to create file");
-        let meta = Meta::from_path(&file_path, false).unwrap();
+        let meta = Meta::from_local(&file_path).await.unwrap();
 
         let icons = Icons::new(true, IconOption::Auto, FlagTheme::Fancy, " ".to_string());
-        let icon = icons.get(&meta.name);
+        let icon = icons.get(&meta.name());
 
         assert_eq!(icon, "\u{f15c} ");
     }
 
-    #[test]
-    fn get_icon_always_tty_default_file() {
+    #[tokio::test]
+    async fn get_icon_always_tty_default_file() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
         let file_path = tmp_dir.path().join("file");
         File::create(&file_path).expect("failed to create file");
-        let meta = Meta::from_path(&file_path, false).unwrap();
+        let meta = Meta::from_local(&file_path).await.unwrap();
 
         let icon = Icons::new(true, IconOption::Always, FlagTheme::Fancy, " ".to_string());
-        let icon_str = icon.get(&meta.name);
+        let icon_str = icon.get(&meta.name());
 
         assert_eq!(icon_str, "\u{f016} "); // 
     }
 
-    #[test]
-    fn get_icon_always_not_tty_default_file() {
+    #[tokio::test]
+    async fn get_icon_always_not_tty_default_file() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
         let file_path = tmp_dir.path().join("file");
         File::create(&file_path).expect("failed to create file");
-        let meta = Meta::from_path(&file_path, false).unwrap();
+        let meta = Meta::from_local(&file_path).await.unwrap();
 
         let icon = Icons::new(false, IconOption::Always, FlagTheme::Fancy, " ".to_string());
-        let icon_str = icon.get(&meta.name);
+        let icon_str = icon.get(&meta.name());
 
         assert_eq!(icon_str, "\u{f016} "); // 
     }
 
-    #[test]
-    fn get_icon_default_file_icon_unicode() {
+    #[tokio::test]
+    async fn get_icon_default_file_icon_unicode() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
         let file_path = tmp_dir.path().join("file");
         File::create(&file_path).expect("failed to create file");
-        let meta = Meta::from_path(&file_path, false).unwrap();
+        let meta = Meta::from_local(&file_path).await.unwrap();
 
         let icon = Icons::new(
             false,
@@ -162,28 +169,28 @@ mod test {
             FlagTheme::Unicode,
             " ".to_string(),
         );
-        let icon_str = icon.get(&meta.name);
+        let icon_str = icon.get(&meta.name());
 
         assert_eq!(icon_str, format!("{}{}", "\u{1f4c4}", icon.icon_separator));
     }
 
-    #[test]
-    fn get_icon_default_directory() {
+    #[tokio::test]
+    async fn get_icon_default_directory() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
         let file_path = tmp_dir.path();
-        let meta = Meta::from_path(file_path, false).unwrap();
+        let meta = Meta::from_local(file_path).await.unwrap();
 
         let icon = Icons::new(false, IconOption::Always, FlagTheme::Fancy, " ".to_string());
-        let icon_str = icon.get(&meta.name);
+        let icon_str = icon.get(&meta.name());
 
         assert_eq!(icon_str, "\u{f115} "); // 
     }
 
-    #[test]
-    fn get_icon_default_directory_unicode() {
+    #[tokio::test]
+    async fn get_icon_default_directory_unicode() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
         let file_path = tmp_dir.path();
-        let meta = Meta::from_path(file_path, false).unwrap();
+        let meta = Meta::from_local(file_path).await.unwrap();
 
         let icon = Icons::new(
             false,
@@ -191,38 +198,38 @@ mod test {
             FlagTheme::Unicode,
             " ".to_string(),
         );
-        let icon_str = icon.get(&meta.name);
+        let icon_str = icon.get(&meta.name());
 
         assert_eq!(icon_str, format!("{}{}", "\u{1f4c2}", icon.icon_separator));
     }
 
-    #[test]
-    fn get_icon_by_name() {
+    #[tokio::test]
+    async fn get_icon_by_name() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
 
         for (file_name, file_icon) in &IconTheme::get_default_icons_by_name() {
             let file_path = tmp_dir.path().join(file_name);
             File::create(&file_path).expect("failed to create file");
-            let meta = Meta::from_path(&file_path, false).unwrap();
+            let meta = Meta::from_local(&file_path).await.unwrap();
 
             let icon = Icons::new(false, IconOption::Always, FlagTheme::Fancy, " ".to_string());
-            let icon_str = icon.get(&meta.name);
+            let icon_str = icon.get(&meta.name());
 
             assert_eq!(icon_str, format!("{}{}", file_icon, icon.icon_separator));
         }
     }
 
-    #[test]
-    fn get_icon_by_extension() {
+    #[tokio::test]
+    async fn get_icon_by_extension() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
 
         for (ext, file_icon) in &IconTheme::get_default_icons_by_extension() {
             let file_path = tmp_dir.path().join(format!("file.{}", ext));
             File::create(&file_path).expect("failed to create file");
-            let meta = Meta::from_path(&file_path, false).unwrap();
+            let meta = Meta::from_local(&file_path).await.unwrap();
 
             let icon = Icons::new(false, IconOption::Always, FlagTheme::Fancy, " ".to_string());
-            let icon_str = icon.get(&meta.name);
+            let icon_str = icon.get(&meta.name());
 
             assert_eq!(icon_str, format!("{}{}", file_icon, icon.icon_separator));
         }
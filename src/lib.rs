@@ -0,0 +1,138 @@
+//! The listing engine of `dl`, to list and render any storage OpenDAL supports from other
+//! Rust programs.
+//!
+//! A [Core](core::Core) lists inputs like `dl` does, either into [Meta](dal::Meta)s or into the
+//! rendered output:
+//!
+//! ```no_run
+//! use clap::Parser;
+//! use dilu::app::Cli;
+//! use dilu::config_file::Config;
+//! use dilu::core::Core;
+//! use dilu::dal::Uri;
+//! use dilu::flags::Flags;
+//! use dilu::ExitCode;
+//!
+//! # async fn list() -> std::io::Result<()> {
+//! let cli = Cli::parse_from(["dl", "-l", "--sort", "-size"]);
+//! let config = Config::with_none();
+//! let flags = Flags::configure_from(&cli, &config).expect("invalid flags");
+//! let uri = Uri::parse("s3://bucket/logs/".as_ref(), &config)?;
+//!
+//! let core = Core::new(flags);
+//! let (metas, exit_code) = core.list(vec![uri]).await;
+//! println!("{}", core.render(&metas));
+//! assert_eq!(ExitCode::OK, exit_code);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The lower level pieces are available too: a [DAL](dal::DAL) lists a single service,
+//! [sort::assemble_sorters] orders the entries by the [Flags](flags::Flags), and [display]
+//! renders them as a grid or a tree.
+
+#![allow(
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss,
+    clippy::match_same_arms,
+    clippy::cast_possible_wrap
+)]
+
+extern crate chrono;
+extern crate chrono_humanize;
+extern crate clap;
+extern crate dirs;
+extern crate libc;
+extern crate lscolors;
+#[cfg(test)]
+extern crate tempfile;
+extern crate term_grid;
+extern crate terminal_size;
+extern crate unicode_width;
+extern crate url;
+extern crate wild;
+extern crate xdg;
+extern crate yaml_rust;
+
+#[cfg(unix)]
+extern crate users;
+
+#[cfg(windows)]
+extern crate windows;
+
+pub mod app;
+pub mod color;
+pub mod commands;
+pub mod config_file;
+pub mod core;
+pub mod dal;
+pub mod display;
+pub mod flags;
+pub mod icon;
+pub mod meta;
+mod record;
+pub mod sort;
+mod theme;
+
+/// The status `dl` exits with, like `ls`: 1 when some entries could not be listed, 2 when an
+/// input could not be listed at all.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Copy, Clone)]
+pub enum ExitCode {
+    OK,
+    MinorIssue,
+    MajorIssue,
+}
+impl ExitCode {
+    pub fn set_if_greater(&mut self, code: ExitCode) {
+        let self_i32 = *self as i32;
+        let code_i32 = code as i32;
+        if self_i32 < code_i32 {
+            *self = code;
+        }
+    }
+}
+/// Macro used to avoid panicking when is used with a pipe and
+/// stderr close before our program.
+#[macro_export]
+macro_rules! print_error {
+    ($($arg:tt)*) => {
+        {
+            use std::io::Write;
+
+            let stderr = std::io::stderr();
+
+            {
+                let mut handle = stderr.lock();
+                // We can write on stderr, so we simply ignore the error and don't print
+                // and stop with success.
+                let res = handle.write_all(std::format!("dl: {}\n\n",
+                                                        std::format!($($arg)*)).as_bytes());
+                if res.is_err() {
+                    std::process::exit(0);
+                }
+            }
+        }
+    };
+}
+
+/// Macro used to avoid panicking when is used with a pipe and
+/// stdout close before our program.
+#[macro_export]
+macro_rules! print_output {
+    ($($arg:tt)*) => {
+        use std::io::Write;
+
+        let stderr = std::io::stdout();
+
+
+        {
+            let mut handle = stderr.lock();
+            // We can write on stdout, so we simply ignore the error and don't print
+            // and stop with success.
+            let res = handle.write_all(std::format!($($arg)*).as_bytes());
+            if res.is_err() {
+                std::process::exit(0);
+            }
+        }
+    };
+}
@@ -1,115 +1,16 @@
-#![allow(
-    clippy::cast_precision_loss,
-    clippy::cast_sign_loss,
-    clippy::match_same_arms,
-    clippy::cast_possible_wrap
-)]
-
-extern crate chrono;
-extern crate chrono_humanize;
-extern crate clap;
-extern crate dirs;
-extern crate libc;
-extern crate lscolors;
-#[cfg(test)]
-extern crate tempfile;
-extern crate term_grid;
-extern crate terminal_size;
-extern crate unicode_width;
-extern crate url;
-extern crate wild;
-extern crate xdg;
-extern crate yaml_rust;
-
-#[cfg(unix)]
-extern crate users;
-
-#[cfg(windows)]
-extern crate windows;
-
-mod app;
-mod color;
-mod config_file;
-mod core;
-mod dal;
-mod display;
-mod flags;
-mod icon;
-mod meta;
-mod sort;
-mod theme;
-
 use clap::Parser;
 
-use crate::app::Cli;
-use crate::config_file::Config;
-use crate::core::Core;
-use crate::flags::Flags;
-
-#[derive(PartialEq, Eq, PartialOrd, Copy, Clone)]
-pub enum ExitCode {
-    OK,
-    MinorIssue,
-    MajorIssue,
-}
-impl ExitCode {
-    pub fn set_if_greater(&mut self, code: ExitCode) {
-        let self_i32 = *self as i32;
-        let code_i32 = code as i32;
-        if self_i32 < code_i32 {
-            *self = code;
-        }
-    }
-}
-/// Macro used to avoid panicking when is used with a pipe and
-/// stderr close before our program.
-#[macro_export]
-macro_rules! print_error {
-    ($($arg:tt)*) => {
-        {
-            use std::io::Write;
-
-            let stderr = std::io::stderr();
-
-            {
-                let mut handle = stderr.lock();
-                // We can write on stderr, so we simply ignore the error and don't print
-                // and stop with success.
-                let res = handle.write_all(std::format!("dl: {}\n\n",
-                                                        std::format!($($arg)*)).as_bytes());
-                if res.is_err() {
-                    std::process::exit(0);
-                }
-            }
-        }
-    };
-}
-
-/// Macro used to avoid panicking when is used with a pipe and
-/// stdout close before our program.
-#[macro_export]
-macro_rules! print_output {
-    ($($arg:tt)*) => {
-        use std::io::Write;
-
-        let stderr = std::io::stdout();
-
-
-        {
-            let mut handle = stderr.lock();
-            // We can write on stdout, so we simply ignore the error and don't print
-            // and stop with success.
-            let res = handle.write_all(std::format!($($arg)*).as_bytes());
-            if res.is_err() {
-                std::process::exit(0);
-            }
-        }
-    };
-}
+use dilu::app::{Cli, Command};
+use dilu::commands;
+use dilu::config_file::Config;
+use dilu::core::Core;
+use dilu::dal::Uri;
+use dilu::flags::Flags;
+use dilu::{print_error, ExitCode};
 
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let cli = Cli::parse_from(wild::args_os());
+async fn main() {
+    let cli = Cli::parse_from(commands::find::long_predicates(wild::args_os()));
 
     let config = if cli.ignore_config {
         Config::with_none()
@@ -118,9 +19,43 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     } else {
         Config::default()
     };
+    let config = config
+        .with_profile_overrides(&cli.inputs)
+        .with_s3_overrides(&cli);
     let flags = Flags::configure_from(&cli, &config).unwrap_or_else(|err| err.exit());
+    #[cfg(debug_assertions)]
+    if let Some(fixture) = &cli.seed {
+        if let Err(err) = dilu::dal::seed(fixture).await {
+            print_error!("{}: {}.", fixture.display(), err);
+            std::process::exit(ExitCode::MajorIssue as i32);
+        }
+    }
+    if let Some(command) = &cli.command {
+        let exit_code = match command {
+            Command::Cat(args) => commands::cat::run(args, &config, flags.concurrency.0).await,
+            Command::Cp(args) => commands::cp::run(args, &config, &flags).await,
+            Command::Du(args) => commands::du::run(args, &config, &flags).await,
+            Command::Find(args) => commands::find::run(args, &config, &flags).await,
+            Command::Rm(args) => commands::rm::run(args, &config, &flags).await,
+            Command::Stat(args) => commands::stat::run(args, &config, &flags).await,
+        };
+        std::process::exit(exit_code as i32);
+    }
+
+    let mut exit_code = ExitCode::OK;
+    let mut uris = Vec::with_capacity(cli.inputs.len());
+    for input in &cli.inputs {
+        match Uri::parse(input, &config) {
+            Ok(uri) => uris.push(uri),
+            Err(err) => {
+                // The error already names the input.
+                print_error!("{}.", err);
+                exit_code.set_if_greater(ExitCode::MajorIssue);
+            }
+        }
+    }
     let core = Core::new(flags);
 
-    core.run(cli.inputs).await
-    // std::process::exit(exit_code as i32);
+    exit_code.set_if_greater(core.run(uris).await);
+    std::process::exit(exit_code as i32);
 }
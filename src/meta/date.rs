@@ -131,10 +131,11 @@ mod test {
         let flags = Flags::default();
 
         assert_eq!(
-            creation_date
-                .format_localized("%c", current_locale())
-                .to_string()
-                .with(Color::AnsiValue(40)),
+            format!(
+                "[{}]",
+                creation_date.format_localized("%c", current_locale())
+            )
+            .with(Color::AnsiValue(40)),
             date.render(&colors, &flags)
         );
 
@@ -158,10 +159,11 @@ mod test {
         let flags = Flags::default();
 
         assert_eq!(
-            creation_date
-                .format_localized("%c", current_locale())
-                .to_string()
-                .with(Color::AnsiValue(42)),
+            format!(
+                "[{}]",
+                creation_date.format_localized("%c", current_locale())
+            )
+            .with(Color::AnsiValue(42)),
             date.render(&colors, &flags)
         );
 
@@ -185,10 +187,11 @@ mod test {
         let flags = Flags::default();
 
         assert_eq!(
-            creation_date
-                .format_localized("%c", current_locale())
-                .to_string()
-                .with(Color::AnsiValue(36)),
+            format!(
+                "[{}]",
+                creation_date.format_localized("%c", current_locale())
+            )
+            .with(Color::AnsiValue(36)),
             date.render(&colors, &flags)
         );
 
@@ -216,7 +219,7 @@ mod test {
         };
 
         assert_eq!(
-            "2 days ago".to_string().with(Color::AnsiValue(36)),
+            "[2 days ago]".to_string().with(Color::AnsiValue(36)),
             date.render(&colors, &flags)
         );
 
@@ -243,7 +246,7 @@ mod test {
         };
 
         assert_eq!(
-            "now".to_string().with(Color::AnsiValue(40)),
+            "[now]".to_string().with(Color::AnsiValue(40)),
             date.render(&colors, &flags)
         );
 
@@ -270,10 +273,7 @@ mod test {
         };
 
         assert_eq!(
-            creation_date
-                .format("%m-%d %R")
-                .to_string()
-                .with(Color::AnsiValue(40)),
+            format!("[{}]", creation_date.format("%m-%d %R")).with(Color::AnsiValue(40)),
             date.render(&colors, &flags)
         );
 
@@ -300,10 +300,7 @@ mod test {
         };
 
         assert_eq!(
-            creation_date
-                .format("%F")
-                .to_string()
-                .with(Color::AnsiValue(36)),
+            format!("[{}]", creation_date.format("%F")).with(Color::AnsiValue(36)),
             date.render(&colors, &flags)
         );
 
@@ -1,11 +1,19 @@
 use crate::color::{ColoredString, Colors, Elem};
+#[cfg(unix)]
+use crate::meta::Permissions;
 use std::fs::Metadata;
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 #[cfg_attr(windows, allow(dead_code))]
 pub enum FileType {
-    Directory,
-    File,
+    BlockDevice,
+    CharDevice,
+    Directory { uid: bool },
+    File { uid: bool, exec: bool },
+    SymLink { is_dir: bool },
+    Pipe,
+    Socket,
+    Special,
 }
 
 impl FileType {
@@ -13,13 +21,39 @@ impl FileType {
     const EXECUTABLE_EXTENSIONS: &[&'static str] = &["exe", "msi", "bat", "ps1"];
 
     #[cfg(unix)]
-    pub fn new(meta: &Metadata) -> Self {
+    pub fn new(
+        meta: &Metadata,
+        symlink_meta: Option<&Metadata>,
+        permissions: &Permissions,
+    ) -> Self {
+        use std::os::unix::fs::FileTypeExt;
+
         let file_type = meta.file_type();
 
-        if file_type.is_dir() {
-            FileType::Directory
+        if file_type.is_file() {
+            FileType::File {
+                exec: permissions.is_executable(),
+                uid: permissions.setuid,
+            }
+        } else if file_type.is_dir() {
+            FileType::Directory {
+                uid: permissions.setuid,
+            }
+        } else if file_type.is_fifo() {
+            FileType::Pipe
+        } else if file_type.is_symlink() {
+            FileType::SymLink {
+                // if broken, defaults to false
+                is_dir: symlink_meta.map(|m| m.is_dir()).unwrap_or_default(),
+            }
+        } else if file_type.is_char_device() {
+            FileType::CharDevice
+        } else if file_type.is_block_device() {
+            FileType::BlockDevice
+        } else if file_type.is_socket() {
+            FileType::Socket
         } else {
-            FileType::File
+            FileType::Special
         }
     }
 
@@ -49,19 +83,26 @@ impl FileType {
             FileType::Special
         }
     }
+
+    pub fn is_dirlike(self) -> bool {
+        matches!(
+            self,
+            FileType::Directory { .. } | FileType::SymLink { is_dir: true }
+        )
+    }
 }
 
 impl FileType {
     pub fn render(self, colors: &Colors) -> ColoredString {
         match self {
-            FileType::File => colors.colorize(
-                '.',
-                &Elem::File {
-                    exec: false,
-                    uid: false,
-                },
-            ),
-            FileType::Directory => colors.colorize('d', &Elem::Dir { uid: false }),
+            FileType::File { exec, .. } => colors.colorize('.', &Elem::File { exec, uid: false }),
+            FileType::Directory { .. } => colors.colorize('d', &Elem::Dir { uid: false }),
+            FileType::Pipe => colors.colorize('|', &Elem::Pipe),
+            FileType::SymLink { .. } => colors.colorize('l', &Elem::SymLink),
+            FileType::BlockDevice => colors.colorize('b', &Elem::BlockDevice),
+            FileType::CharDevice => colors.colorize('c', &Elem::CharDevice),
+            FileType::Socket => colors.colorize('s', &Elem::Socket),
+            FileType::Special => colors.colorize('?', &Elem::Special),
         }
     }
 }
@@ -101,18 +142,19 @@ mod test {
         );
     }
 
-    #[test]
-    fn test_dir_type() {
+    #[tokio::test]
+    async fn test_dir_type() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
         #[cfg(not(windows))]
-        let meta = crate::meta::Meta::from_path(tmp_dir.path(), false)
+        let meta = crate::dal::Meta::from_local(tmp_dir.path())
+            .await
             .expect("failed to get tempdir path");
         let metadata = tmp_dir.path().metadata().expect("failed to get metas");
 
         let colors = Colors::new(ThemeOption::NoLscolors);
 
         #[cfg(not(windows))]
-        let file_type = FileType::new(&metadata, None, &meta.permissions.unwrap());
+        let file_type = FileType::new(&metadata, None, &meta.permissions().unwrap());
         #[cfg(windows)]
         let file_type = FileType::new(&metadata, None, tmp_dir.path());
 
@@ -0,0 +1,55 @@
+use crate::color::{ColoredString, Colors, Elem};
+use std::fs::Metadata;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Links {
+    nlink: Option<u64>,
+}
+
+impl From<&Metadata> for Links {
+    #[cfg(unix)]
+    fn from(meta: &Metadata) -> Self {
+        use std::os::unix::fs::MetadataExt;
+
+        let nlink = meta.nlink();
+
+        Self { nlink: Some(nlink) }
+    }
+
+    #[cfg(windows)]
+    fn from(_: &Metadata) -> Self {
+        Self { nlink: None }
+    }
+}
+
+impl Links {
+    pub fn count(&self) -> Option<u64> {
+        self.nlink
+    }
+
+    pub fn render(&self, colors: &Colors) -> ColoredString {
+        match self.nlink {
+            Some(i) => colors.colorize(i.to_string(), &Elem::Links { valid: true }),
+            None => colors.colorize(String::from("-"), &Elem::Links { valid: false }),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(unix)]
+mod tests {
+    use super::Links;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_hard_links() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path().join("file.txt");
+        fs::write(&file_path, "").expect("failed to create file");
+        assert_eq!(Some(1), Links::from(&file_path.metadata().unwrap()).count());
+
+        fs::hard_link(&file_path, tmp_dir.path().join("link.txt")).expect("failed to link");
+        assert_eq!(Some(2), Links::from(&file_path.metadata().unwrap()).count());
+    }
+}
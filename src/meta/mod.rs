@@ -1,10 +1,22 @@
-pub mod filetype;
-pub mod size;
 pub mod date;
-pub mod name;
+pub mod filetype;
+pub mod indicator;
+pub mod inode;
+pub mod links;
 pub mod locale;
+pub mod name;
+pub mod owner;
+pub mod permissions;
+pub mod size;
+pub mod symlink;
 
 pub use self::date::Date;
 pub use self::filetype::FileType;
+pub use self::indicator::Indicator;
+pub use self::inode::INode;
+pub use self::links::Links;
 pub use self::name::Name;
+pub use self::owner::Owner;
+pub use self::permissions::Permissions;
 pub use self::size::Size;
+pub use self::symlink::SymLink;
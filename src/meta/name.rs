@@ -5,7 +5,6 @@ use crate::meta::filetype::FileType;
 use crate::print_error;
 use crate::url::Url;
 use std::cmp::{Ordering, PartialOrd};
-use std::ffi::OsStr;
 use std::path::{Component, Path, PathBuf};
 
 #[derive(Debug)]
@@ -21,6 +20,7 @@ pub struct Name {
     path: PathBuf,
     extension: Option<String>,
     file_type: FileType,
+    broken_link: bool,
 }
 
 impl Name {
@@ -41,14 +41,18 @@ impl Name {
             path,
             extension,
             file_type,
+            broken_link: false,
         }
     }
 
+    /// Mark the name as the one of a symbolic link whose target is missing.
+    pub fn with_broken_link(mut self, broken_link: bool) -> Self {
+        self.broken_link = broken_link;
+        self
+    }
+
     pub fn file_name(&self) -> &str {
-        self.path
-            .file_name()
-            .and_then(OsStr::to_str)
-            .unwrap_or(&self.name)
+        &self.name
     }
 
     fn relative_path<T: AsRef<Path> + Clone>(&self, base_path: T) -> PathBuf {
@@ -177,9 +181,16 @@ impl Name {
             ),
         };
 
+        if self.broken_link {
+            return colors.colorize(content, &Elem::BrokenSymLink);
+        }
+
         let elem = match self.file_type {
-            FileType::Directory => Elem::Dir { uid: false },
-            FileType::File => Elem::File {
+            FileType::CharDevice => Elem::CharDevice,
+            FileType::Directory { uid } => Elem::Dir { uid },
+            FileType::SymLink { .. } => Elem::SymLink,
+            FileType::File { uid, exec } => Elem::File { uid, exec },
+            _ => Elem::File {
                 exec: false,
                 uid: false,
             },
@@ -222,10 +233,10 @@ mod test {
     use super::DisplayOption;
     use super::Name;
     use crate::color::{self, Colors};
+    use crate::dal::Meta;
     use crate::flags::{HyperlinkOption, IconOption, IconTheme as FlagTheme};
     use crate::icon::Icons;
     use crate::meta::FileType;
-    use crate::meta::Meta;
     #[cfg(unix)]
     use crate::meta::Permissions;
     use crate::url::Url;
@@ -266,21 +277,21 @@ mod test {
         );
     }
 
-    #[test]
-    fn test_print_dir_name() {
+    #[tokio::test]
+    async fn test_print_dir_name() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
         let icons = &Icons::new(false, IconOption::Always, FlagTheme::Fancy, " ".to_string());
 
         // Create the directory
         let dir_path = tmp_dir.path().join("directory");
         fs::create_dir(&dir_path).expect("failed to create the dir");
-        let meta = Meta::from_path(&dir_path, false).unwrap();
+        let meta = Meta::from_local(&dir_path).await.unwrap();
 
         let colors = Colors::new(color::ThemeOption::NoLscolors);
 
         assert_eq!(
             " directory".to_string().with(Color::AnsiValue(33)),
-            meta.name.render(
+            meta.name().render(
                 &colors,
                 icons,
                 &DisplayOption::FileName,
@@ -390,21 +401,21 @@ mod test {
         );
     }
 
-    #[test]
-    fn test_print_without_icon_or_color() {
+    #[tokio::test]
+    async fn test_print_without_icon_or_color() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
         let icons = Icons::new(false, IconOption::Never, FlagTheme::Fancy, " ".to_string());
 
         // Create the file;
         let file_path = tmp_dir.path().join("file.txt");
         File::create(&file_path).expect("failed to create file");
-        let meta = Meta::from_path(&file_path, false).unwrap();
+        let meta = Meta::from_local(&file_path).await.unwrap();
 
         let colors = Colors::new(color::ThemeOption::NoColor);
 
         assert_eq!(
             "file.txt",
-            meta.name
+            meta.name()
                 .render(
                     &colors,
                     &icons,
@@ -416,15 +427,15 @@ mod test {
         );
     }
 
-    #[test]
-    fn test_print_hyperlink() {
+    #[tokio::test]
+    async fn test_print_hyperlink() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
         let icons = Icons::new(false, IconOption::Never, FlagTheme::Fancy, " ".to_string());
 
         // Create the file;
         let file_path = tmp_dir.path().join("file.txt");
         File::create(&file_path).expect("failed to create file");
-        let meta = Meta::from_path(&file_path, false).unwrap();
+        let meta = Meta::from_local(&file_path).await.unwrap();
 
         let colors = Colors::new(color::ThemeOption::NoColor);
 
@@ -437,7 +448,7 @@ mod test {
 
         assert_eq!(
             expected_text,
-            meta.name
+            meta.name()
                 .render(
                     &colors,
                     &icons,
@@ -596,8 +607,9 @@ mod test {
 
     #[test]
     fn test_parent_relative_path() {
+        // The listed entries have paths without a leading `/`.
         let name = Name::new(
-            Path::new("/home/parent1/child"),
+            Path::new("home/parent1/child"),
             FileType::File {
                 uid: false,
                 exec: false,
@@ -614,7 +626,7 @@ mod test {
     #[test]
     fn test_current_relative_path() {
         let name = Name::new(
-            Path::new("/home/parent1/child"),
+            Path::new("home/parent1/child"),
             FileType::File {
                 uid: false,
                 exec: false,
@@ -628,7 +640,7 @@ mod test {
     #[test]
     fn test_grand_parent_relative_path() {
         let name = Name::new(
-            Path::new("/home/grand-parent1/parent1/child"),
+            Path::new("home/grand-parent1/parent1/child"),
             FileType::File {
                 uid: false,
                 exec: false,
@@ -0,0 +1,75 @@
+use crate::color::{ColoredString, Colors, Elem};
+#[cfg(unix)]
+use std::fs::Metadata;
+#[cfg(unix)]
+use users::{get_group_by_gid, get_user_by_uid};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Owner {
+    user: String,
+    group: String,
+}
+
+#[cfg(unix)]
+impl From<&Metadata> for Owner {
+    fn from(meta: &Metadata) -> Self {
+        use std::os::unix::fs::MetadataExt;
+
+        // Fall back to the ids when the names are unknown, like `ls` does.
+        let user = match get_user_by_uid(meta.uid()) {
+            Some(res) => res.name().to_string_lossy().to_string(),
+            None => meta.uid().to_string(),
+        };
+
+        let group = match get_group_by_gid(meta.gid()) {
+            Some(res) => res.name().to_string_lossy().to_string(),
+            None => meta.gid().to_string(),
+        };
+
+        Self { user, group }
+    }
+}
+
+impl Owner {
+    pub fn user(&self) -> &str {
+        &self.user
+    }
+
+    pub fn group(&self) -> &str {
+        &self.group
+    }
+
+    pub fn render_user(&self, colors: &Colors) -> ColoredString {
+        colors.colorize(self.user.clone(), &Elem::User)
+    }
+
+    pub fn render_group(&self, colors: &Colors) -> ColoredString {
+        colors.colorize(self.group.clone(), &Elem::Group)
+    }
+}
+
+#[cfg(unix)]
+#[cfg(test)]
+mod test {
+    use super::Owner;
+    use crate::color::{Colors, ThemeOption};
+    use std::fs::File;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_owner_of_created_file() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path().join("file.txt");
+        File::create(&file_path).expect("failed to create file");
+        let meta = file_path.metadata().expect("failed to get meta");
+
+        let owner = Owner::from(&meta);
+        let user = users::get_current_username()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| users::get_current_uid().to_string());
+        assert_eq!(user, owner.user());
+
+        let colors = Colors::new(ThemeOption::NoColor);
+        assert_eq!(user, owner.render_user(&colors).content().as_str());
+    }
+}
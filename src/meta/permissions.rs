@@ -0,0 +1,217 @@
+use crate::color::{ColoredString, Colors, Elem};
+use crate::flags::{Flags, PermissionFlag};
+use std::fs::Metadata;
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Permissions {
+    pub user_read: bool,
+    pub user_write: bool,
+    pub user_execute: bool,
+
+    pub group_read: bool,
+    pub group_write: bool,
+    pub group_execute: bool,
+
+    pub other_read: bool,
+    pub other_write: bool,
+    pub other_execute: bool,
+
+    pub sticky: bool,
+    pub setgid: bool,
+    pub setuid: bool,
+}
+
+impl From<&Metadata> for Permissions {
+    #[cfg(unix)]
+    fn from(meta: &Metadata) -> Self {
+        use std::os::unix::fs::PermissionsExt;
+
+        let bits = meta.permissions().mode();
+        let has_bit = |bit| bits & bit == bit;
+
+        Self {
+            user_read: has_bit(modes::USER_READ),
+            user_write: has_bit(modes::USER_WRITE),
+            user_execute: has_bit(modes::USER_EXECUTE),
+
+            group_read: has_bit(modes::GROUP_READ),
+            group_write: has_bit(modes::GROUP_WRITE),
+            group_execute: has_bit(modes::GROUP_EXECUTE),
+
+            other_read: has_bit(modes::OTHER_READ),
+            other_write: has_bit(modes::OTHER_WRITE),
+            other_execute: has_bit(modes::OTHER_EXECUTE),
+
+            sticky: has_bit(modes::STICKY),
+            setgid: has_bit(modes::SETGID),
+            setuid: has_bit(modes::SETUID),
+        }
+    }
+
+    #[cfg(windows)]
+    fn from(meta: &Metadata) -> Self {
+        // Windows only knows whether a file is read only.
+        let write = !meta.permissions().readonly();
+        Self {
+            user_read: true,
+            user_write: write,
+            user_execute: false,
+
+            group_read: true,
+            group_write: write,
+            group_execute: false,
+
+            other_read: true,
+            other_write: write,
+            other_execute: false,
+
+            sticky: false,
+            setgid: false,
+            setuid: false,
+        }
+    }
+}
+
+impl Permissions {
+    fn bits_to_octal(r: bool, w: bool, x: bool) -> u8 {
+        (r as u8) * 4 + (w as u8) * 2 + (x as u8)
+    }
+
+    pub fn render(&self, colors: &Colors, flags: &Flags) -> ColoredString {
+        let bit = |bit, chr: &'static str, elem: &Elem| {
+            if bit {
+                colors.colorize(chr, elem)
+            } else {
+                colors.colorize('-', &Elem::NoAccess)
+            }
+        };
+
+        let strings = match flags.permission {
+            PermissionFlag::Rwx => vec![
+                // User permissions
+                bit(self.user_read, "r", &Elem::Read),
+                bit(self.user_write, "w", &Elem::Write),
+                match (self.user_execute, self.setuid) {
+                    (false, false) => colors.colorize('-', &Elem::NoAccess),
+                    (true, false) => colors.colorize('x', &Elem::Exec),
+                    (false, true) => colors.colorize('S', &Elem::ExecSticky),
+                    (true, true) => colors.colorize('s', &Elem::ExecSticky),
+                },
+                // Group permissions
+                bit(self.group_read, "r", &Elem::Read),
+                bit(self.group_write, "w", &Elem::Write),
+                match (self.group_execute, self.setgid) {
+                    (false, false) => colors.colorize('-', &Elem::NoAccess),
+                    (true, false) => colors.colorize('x', &Elem::Exec),
+                    (false, true) => colors.colorize('S', &Elem::ExecSticky),
+                    (true, true) => colors.colorize('s', &Elem::ExecSticky),
+                },
+                // Other permissions
+                bit(self.other_read, "r", &Elem::Read),
+                bit(self.other_write, "w", &Elem::Write),
+                match (self.other_execute, self.sticky) {
+                    (false, false) => colors.colorize('-', &Elem::NoAccess),
+                    (true, false) => colors.colorize('x', &Elem::Exec),
+                    (false, true) => colors.colorize('T', &Elem::ExecSticky),
+                    (true, true) => colors.colorize('t', &Elem::ExecSticky),
+                },
+            ],
+            PermissionFlag::Octal => {
+                let octals = [
+                    Self::bits_to_octal(self.setuid, self.setgid, self.sticky),
+                    Self::bits_to_octal(self.user_read, self.user_write, self.user_execute),
+                    Self::bits_to_octal(self.group_read, self.group_write, self.group_execute),
+                    Self::bits_to_octal(self.other_read, self.other_write, self.other_execute),
+                ];
+                let octal: String = octals.iter().map(|o| o.to_string()).collect();
+                vec![colors.colorize(octal, &Elem::Octal)]
+            }
+        };
+
+        let res = strings
+            .into_iter()
+            .fold(String::with_capacity(10), |mut acc, x| {
+                acc.push_str(&x.to_string());
+                acc
+            });
+        ColoredString::new(Colors::default_style(), res)
+    }
+
+    pub fn is_executable(&self) -> bool {
+        self.user_execute || self.group_execute || self.other_execute
+    }
+}
+
+// More readable aliases for the permission bits exposed by libc.
+#[allow(trivial_numeric_casts)]
+#[cfg(unix)]
+mod modes {
+    pub type Mode = u32;
+    // The `libc::mode_t` type’s actual type varies, but the value returned
+    // from `metadata.permissions().mode()` is always `u32`.
+
+    pub const USER_READ: Mode = libc::S_IRUSR as Mode;
+    pub const USER_WRITE: Mode = libc::S_IWUSR as Mode;
+    pub const USER_EXECUTE: Mode = libc::S_IXUSR as Mode;
+
+    pub const GROUP_READ: Mode = libc::S_IRGRP as Mode;
+    pub const GROUP_WRITE: Mode = libc::S_IWGRP as Mode;
+    pub const GROUP_EXECUTE: Mode = libc::S_IXGRP as Mode;
+
+    pub const OTHER_READ: Mode = libc::S_IROTH as Mode;
+    pub const OTHER_WRITE: Mode = libc::S_IWOTH as Mode;
+    pub const OTHER_EXECUTE: Mode = libc::S_IXOTH as Mode;
+
+    pub const STICKY: Mode = libc::S_ISVTX as Mode;
+    pub const SETGID: Mode = libc::S_ISGID as Mode;
+    pub const SETUID: Mode = libc::S_ISUID as Mode;
+}
+
+#[cfg(unix)]
+#[cfg(test)]
+mod test {
+    use super::Permissions;
+    use crate::app::Cli;
+    use crate::color::{Colors, ThemeOption};
+    use crate::config_file::Config;
+    use crate::flags::Flags;
+
+    use clap::Parser;
+    use std::fs::{self, File};
+    use std::os::unix::fs::PermissionsExt;
+    use tempfile::tempdir;
+
+    fn render(mode: u32, argv: &[&str]) -> String {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path().join("file.txt");
+        File::create(&file_path).expect("failed to create file");
+        fs::set_permissions(&file_path, fs::Permissions::from_mode(mode))
+            .expect("failed to set permissions");
+        let meta = file_path.metadata().expect("failed to get meta");
+
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+        let colors = Colors::new(ThemeOption::NoColor);
+        Permissions::from(&meta)
+            .render(&colors, &flags)
+            .content()
+            .to_string()
+    }
+
+    #[test]
+    fn test_permission_rwx() {
+        assert_eq!("rw-r--r--", render(0o644, &["lsd"]));
+        assert_eq!("rwxr-x--x", render(0o751, &["lsd"]));
+    }
+
+    #[test]
+    fn test_permission_rwx_special() {
+        assert_eq!("rwsr-Sr-t", render(0o7745, &["lsd"]));
+    }
+
+    #[test]
+    fn test_permission_octal() {
+        assert_eq!("0644", render(0o644, &["lsd", "--permission", "octal"]));
+        assert_eq!("1755", render(0o1755, &["lsd", "--permission", "octal"]));
+    }
+}
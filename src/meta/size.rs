@@ -36,6 +36,32 @@ impl Size {
         self.bytes
     }
 
+    /// Parse a size written like it is rendered, e.g. `42`, `1.5 KB` or `3G`, the units being
+    /// multiples of 1024 and case insensitive.
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let value = value.trim();
+        let split = value
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(value.len());
+        let (number, unit) = value.split_at(split);
+        let number: f64 = number
+            .parse()
+            .map_err(|_| format!("invalid size {value}, expected a number and a unit"))?;
+        let unit = match unit.trim().to_ascii_uppercase().as_str() {
+            "" | "B" => 1,
+            "K" | "KB" => KB,
+            "M" | "MB" => MB,
+            "G" | "GB" => GB,
+            "T" | "TB" => TB,
+            _ => {
+                return Err(format!(
+                    "invalid size unit in {value}, expected B, KB, MB, GB or TB"
+                ))
+            }
+        };
+        Ok(Self::new((number * unit as f64).round() as u64))
+    }
+
     fn format_size(&self, number: f64) -> String {
         format!("{0:.1$}", number, if number < 10.0 { 1 } else { 0 })
     }
@@ -326,4 +352,16 @@ mod test {
         assert_eq!(size.render(&colors, &flags, Some(2)).to_string(), "42K");
         assert_eq!(size.render(&colors, &flags, Some(3)).to_string(), " 42K");
     }
+
+    #[test]
+    fn parse_rendered_sizes() {
+        assert_eq!(Size::parse("42"), Ok(Size::new(42)));
+        assert_eq!(Size::parse("42 B"), Ok(Size::new(42)));
+        assert_eq!(Size::parse("1.5 KB"), Ok(Size::new(1536)));
+        assert_eq!(Size::parse("2M"), Ok(Size::new(2 * MB)));
+        assert_eq!(Size::parse("3gb"), Ok(Size::new(3 * GB)));
+        assert_eq!(Size::parse("1T"), Ok(Size::new(TB)));
+        assert!(Size::parse("MB").is_err());
+        assert!(Size::parse("42 PB").is_err());
+    }
 }
@@ -0,0 +1,119 @@
+use crate::color::{ColoredString, Colors, Elem};
+use crate::flags::Flags;
+use std::fs::read_link;
+use std::path::Path;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SymLink {
+    target: Option<String>,
+    valid: bool,
+}
+
+impl From<&Path> for SymLink {
+    fn from(path: &Path) -> Self {
+        if let Ok(target) = read_link(path) {
+            // Relative targets are resolved from the directory of the link.
+            let valid = match path.parent() {
+                Some(parent) if target.is_relative() => parent.join(&target).exists(),
+                _ => target.exists(),
+            };
+            return Self {
+                target: Some(target.to_string_lossy().to_string()),
+                valid,
+            };
+        }
+
+        Self {
+            target: None,
+            valid: false,
+        }
+    }
+}
+
+impl SymLink {
+    pub fn symlink_string(&self) -> Option<String> {
+        self.target.as_ref().map(|t| t.to_string())
+    }
+
+    pub fn is_broken(&self) -> bool {
+        self.target.is_some() && !self.valid
+    }
+
+    pub fn render(&self, colors: &Colors, flags: &Flags) -> ColoredString {
+        if let Some(target_string) = self.symlink_string() {
+            let elem = if self.valid {
+                &Elem::SymLink
+            } else {
+                &Elem::MissingSymLinkTarget
+            };
+
+            let strings: &[ColoredString] = &[
+                ColoredString::new(
+                    Colors::default_style(),
+                    format!(" {} ", flags.symlink_arrow),
+                ),
+                colors.colorize(target_string, elem),
+            ];
+
+            let res = strings
+                .iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<String>>()
+                .join("");
+            ColoredString::new(Colors::default_style(), res)
+        } else {
+            ColoredString::new(Colors::default_style(), "".into())
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(unix)]
+mod tests {
+    use super::SymLink;
+    use crate::app::Cli;
+    use crate::color::{Colors, ThemeOption};
+    use crate::config_file::Config;
+    use crate::flags::Flags;
+
+    use clap::Parser;
+    use std::fs::File;
+    use std::os::unix::fs::symlink;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_symlink_render_default_valid_target() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        File::create(tmp_dir.path().join("target")).expect("failed to create file");
+        let link = tmp_dir.path().join("link");
+        symlink("target", &link).expect("failed to create symlink");
+
+        let cli = Cli::try_parse_from(["lsd"]).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+        let symlink = SymLink::from(link.as_path());
+        assert!(!symlink.is_broken());
+        assert_eq!(
+            " ⇒ target",
+            symlink
+                .render(&Colors::new(ThemeOption::NoColor), &flags)
+                .content()
+        );
+    }
+
+    #[test]
+    fn test_symlink_broken() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let link = tmp_dir.path().join("link");
+        symlink("missing", &link).expect("failed to create symlink");
+
+        assert!(SymLink::from(link.as_path()).is_broken());
+    }
+
+    #[test]
+    fn test_not_a_symlink() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let symlink = SymLink::from(tmp_dir.path());
+        assert_eq!(None, symlink.symlink_string());
+        assert!(!symlink.is_broken());
+    }
+}
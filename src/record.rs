@@ -0,0 +1,291 @@
+//! This module serialises the [Meta]s for other programs, see [Format](crate::flags::Format).
+
+use crate::color::{Colors, ThemeOption};
+use crate::dal::Meta;
+use crate::flags::{Block, Display, Flags, Layout};
+use crate::meta::{Date, FileType};
+
+use serde::Serialize;
+
+/// The serialised form of a [Meta].
+#[derive(Debug, Serialize)]
+pub struct Record<'a> {
+    path: String,
+    name: String,
+    #[serde(rename = "type")]
+    file_type: &'static str,
+    size: Option<u64>,
+    modified: Option<String>,
+    content_type: Option<&'a str>,
+    etag: Option<&'a str>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    sub_metas: Vec<Record<'a>>,
+}
+
+impl<'a> Record<'a> {
+    /// The record of `meta` alone, without its `sub_metas`.
+    pub fn new(meta: &'a Meta) -> Self {
+        Self {
+            path: meta.path.to_string_lossy().to_string(),
+            name: meta.name().file_name().to_string(),
+            file_type: type_name(meta.file_type()),
+            size: meta.size().map(|s| s.get_bytes()),
+            modified: modified(meta),
+            content_type: meta.content_type(),
+            etag: meta.etag(),
+            sub_metas: vec![],
+        }
+    }
+
+    /// The record of `meta` with all of its `sub_metas`.
+    fn nested(meta: &'a Meta) -> Self {
+        let mut record = Self::new(meta);
+        record.sub_metas = meta.sub_metas.iter().map(Self::nested).collect();
+        record
+    }
+}
+
+/// Serialise the entries as one JSON array.
+pub fn json(metas: &[Meta], flags: &Flags) -> String {
+    let records: Vec<Record> = displayed(metas, flags)
+        .into_iter()
+        .map(Record::nested)
+        .collect();
+    serde_json::to_string(&records).expect("failed to serialise the entries") + "\n"
+}
+
+/// Serialise one entry as a line of NDJSON.
+pub fn ndjson_line(meta: &Meta) -> String {
+    serde_json::to_string(&Record::new(meta)).expect("failed to serialise the entry") + "\n"
+}
+
+/// Serialise the entries as lines of NDJSON, the `sub_metas` flattened after their directory
+/// like they are streamed.
+pub fn ndjson(metas: &[Meta], flags: &Flags) -> String {
+    let mut output = String::new();
+    let mut stack: Vec<&Meta> = displayed(metas, flags);
+    stack.reverse();
+    while let Some(meta) = stack.pop() {
+        output += &ndjson_line(meta);
+        stack.extend(meta.sub_metas.iter().rev());
+    }
+    output
+}
+
+/// Serialise the entries as a header row followed by one row per entry, with one column per
+/// [Block]. The `sub_metas` are flattened after their directory, with a leading `path` column
+/// telling where each of them is, as their names alone do not.
+pub fn delimited(metas: &[Meta], flags: &Flags, separator: char) -> String {
+    let with_path = flags.recursion.enabled || flags.layout == Layout::Tree;
+    let mut header: Vec<&str> = flags.blocks.0.iter().map(column).collect();
+    if with_path {
+        header.insert(0, "path");
+    }
+    let mut output = header.join(&separator.to_string());
+    output.push('\n');
+
+    let mut stack: Vec<&Meta> = displayed(metas, flags);
+    stack.reverse();
+    while let Some(meta) = stack.pop() {
+        let mut row: Vec<String> = flags
+            .blocks
+            .0
+            .iter()
+            .map(|block| escape(&value(meta, block, flags), separator))
+            .collect();
+        if with_path {
+            row.insert(0, escape(&meta.path.to_string_lossy(), separator));
+        }
+        output += &row.join(&separator.to_string());
+        output.push('\n');
+
+        stack.extend(meta.sub_metas.iter().rev());
+    }
+    output
+}
+
+/// The name of `file_type` in the records.
+pub fn type_name(file_type: FileType) -> &'static str {
+    match file_type {
+        FileType::Directory { .. } => "directory",
+        FileType::File { .. } => "file",
+        FileType::SymLink { .. } => "symlink",
+        FileType::Pipe => "pipe",
+        FileType::Socket => "socket",
+        FileType::CharDevice => "char-device",
+        FileType::BlockDevice => "block-device",
+        FileType::Special => "special",
+    }
+}
+
+/// The entries to serialise: the directories given by the user are replaced by their content,
+/// unless they are displayed themselves, like `display::grid` does.
+fn displayed<'a>(metas: &'a [Meta], flags: &Flags) -> Vec<&'a Meta> {
+    let expand = flags.layout != Layout::Tree && flags.display != Display::DirectoryOnly;
+    let mut entries = vec![];
+    for meta in metas {
+        if expand && meta.lists_content(flags) {
+            entries.extend(meta.sub_metas.iter());
+        } else {
+            entries.push(meta);
+        }
+    }
+    entries
+}
+
+/// The last modified date of `meta` in RFC 3339, if the service returned it.
+pub fn modified(meta: &Meta) -> Option<String> {
+    match meta.modified_date() {
+        Date::Date(date) => Some(date.to_rfc3339()),
+        Date::Invalid => None,
+    }
+}
+
+fn column(block: &Block) -> &'static str {
+    match block {
+        Block::Permission => "permission",
+        Block::User => "user",
+        Block::Group => "group",
+        Block::INode => "inode",
+        Block::Links => "links",
+        Block::Size => "size",
+        Block::SizeValue => "size_value",
+        Block::Date => "date",
+        Block::Name => "name",
+        Block::ContentType => "content_type",
+        Block::Etag => "etag",
+        Block::ContentMd5 => "content_md5",
+        Block::ContentDisposition => "content_disposition",
+    }
+}
+
+fn value(meta: &Meta, block: &Block, flags: &Flags) -> String {
+    match block {
+        Block::Permission => meta
+            .permissions()
+            .map(|p| {
+                p.render(&Colors::new(ThemeOption::NoColor), flags)
+                    .content()
+                    .to_string()
+            })
+            .unwrap_or_default(),
+        Block::User => meta
+            .owner()
+            .map(|o| o.user().to_string())
+            .unwrap_or_default(),
+        Block::Group => meta
+            .owner()
+            .map(|o| o.group().to_string())
+            .unwrap_or_default(),
+        Block::INode => meta
+            .inode()
+            .and_then(|i| i.index())
+            .map(|i| i.to_string())
+            .unwrap_or_default(),
+        Block::Links => meta
+            .links()
+            .and_then(|l| l.count())
+            .map(|l| l.to_string())
+            .unwrap_or_default(),
+        Block::Size | Block::SizeValue => meta
+            .size()
+            .map(|s| s.get_bytes().to_string())
+            .unwrap_or_default(),
+        Block::Date => modified(meta).unwrap_or_default(),
+        Block::Name => meta.name().file_name().to_string(),
+        Block::ContentType => meta.content_type().unwrap_or_default().to_string(),
+        Block::Etag => meta.etag().unwrap_or_default().to_string(),
+        Block::ContentMd5 => meta.content_md5().unwrap_or_default().to_string(),
+        Block::ContentDisposition => meta.content_disposition().unwrap_or_default().to_string(),
+    }
+}
+
+/// Quote a CSV field as in RFC 4180, or escape the special characters of a TSV field.
+fn escape(field: &str, separator: char) -> String {
+    if separator == '\t' {
+        return field
+            .replace('\\', "\\\\")
+            .replace('\t', "\\t")
+            .replace('\n', "\\n")
+            .replace('\r', "\\r");
+    }
+
+    if field.contains([separator, '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{delimited, escape, json, ndjson};
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::dal::{Uri, DAL};
+    use crate::flags::Flags;
+
+    use assert_fs::prelude::*;
+    use clap::Parser;
+    use std::path::Path;
+
+    #[tokio::test]
+    async fn test_json_and_delimited() {
+        let cli = Cli::try_parse_from(["lsd", "--blocks", "size,name", "-R"]).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("a,b/file").write_str("12345").unwrap();
+
+        let uri = Uri::parse(dir.path(), &Config::with_none()).unwrap();
+        let dal = DAL::new(Path::new("/"), &uri, 4).unwrap();
+        let mut meta = dal.from_path(&uri.path).await.unwrap();
+        meta.sub_metas = dal.recurse_into(&meta, usize::MAX, &flags).await.unwrap().0;
+        let metas = vec![meta];
+
+        let value: serde_json::Value = serde_json::from_str(&json(&metas, &flags)).unwrap();
+        assert_eq!("a,b", value[0]["name"]);
+        assert_eq!("directory", value[0]["type"]);
+        assert_eq!("file", value[0]["sub_metas"][0]["name"]);
+        assert_eq!(5, value[0]["sub_metas"][0]["size"]);
+
+        let csv = delimited(&metas, &flags, ',');
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!("path,size,name", rows[0]);
+        assert!(rows[1].ends_with(",\"a,b\""));
+        // The paths are relative to the root of the service, like in the JSON records.
+        let file = dir.child("a,b/file");
+        let path = escape(file.path().to_string_lossy().trim_start_matches('/'), ',');
+        assert_eq!(format!("{path},5,file"), rows[2]);
+
+        let cli = Cli::try_parse_from(["lsd", "--blocks", "size,name"]).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+        let csv = delimited(&metas, &flags, ',');
+        assert_eq!(Some("size,name"), csv.lines().next());
+
+        let lines: Vec<serde_json::Value> = ndjson(&metas, &flags)
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(2, lines.len());
+        assert_eq!("a,b", lines[0]["name"]);
+        assert_eq!(5, lines[1]["size"]);
+    }
+
+    #[test]
+    fn test_escape_csv() {
+        assert_eq!("plain", escape("plain", ','));
+        assert_eq!("\"a,b\"", escape("a,b", ','));
+        assert_eq!("\"say \"\"hi\"\"\"", escape("say \"hi\"", ','));
+        assert_eq!("\"two\nlines\"", escape("two\nlines", ','));
+    }
+
+    #[test]
+    fn test_escape_tsv() {
+        assert_eq!("a,b", escape("a,b", '\t'));
+        assert_eq!("a\\tb", escape("a\tb", '\t'));
+        assert_eq!("two\\nlines", escape("two\nlines", '\t'));
+        assert_eq!("back\\\\slash", escape("back\\slash", '\t'));
+    }
+}
@@ -1,6 +1,5 @@
 use crate::dal::Meta;
 use crate::flags::{DirGrouping, Flags, SortColumn, SortOrder};
-use crate::meta::FileType;
 use human_sort::compare;
 use std::cmp::Ordering;
 
@@ -18,13 +17,22 @@ pub fn assemble_sorters(flags: &Flags) -> Vec<(SortOrder, SortFn)> {
         DirGrouping::None => {}
     };
 
-    match flags.sorting.column {
-        SortColumn::Name => sorters.push((flags.sorting.order, by_name)),
-        SortColumn::Size => sorters.push((flags.sorting.order, by_size)),
-        SortColumn::Time => sorters.push((flags.sorting.order, by_date)),
-        SortColumn::Version => sorters.push((flags.sorting.order, by_version)),
-        SortColumn::Extension => sorters.push((flags.sorting.order, by_extension)),
-        SortColumn::None => {}
+    for key in &flags.sorting.columns.0 {
+        let order = match flags.sorting.order {
+            SortOrder::Default => key.order,
+            SortOrder::Reverse => key.order.reverse(),
+        };
+        let sorter: SortFn = match key.column {
+            SortColumn::Name => by_name,
+            SortColumn::Size => by_size,
+            SortColumn::Time => by_date,
+            SortColumn::Version => by_version,
+            SortColumn::Extension => by_extension,
+            SortColumn::ContentType => by_content_type,
+            SortColumn::Etag => by_etag,
+            SortColumn::Path => by_path,
+        };
+        sorters.push((order, sorter));
     }
     sorters
 }
@@ -45,7 +53,7 @@ pub fn by_meta(sorters: &[(SortOrder, SortFn)], a: &Meta, b: &Meta) -> Ordering
 }
 
 fn with_dirs_first(a: &Meta, b: &Meta) -> Ordering {
-    matches!(b.file_type(), FileType::Directory).cmp(&matches!(a.file_type(), FileType::Directory))
+    b.file_type().is_dirlike().cmp(&a.file_type().is_dirlike())
 }
 
 fn by_size(a: &Meta, b: &Meta) -> Ordering {
@@ -75,28 +83,47 @@ fn by_extension(a: &Meta, b: &Meta) -> Ordering {
     a.name().extension().cmp(&b.name().extension())
 }
 
+fn by_content_type(a: &Meta, b: &Meta) -> Ordering {
+    a.content_type().cmp(&b.content_type())
+}
+
+fn by_etag(a: &Meta, b: &Meta) -> Ordering {
+    a.etag().cmp(&b.etag())
+}
+
+fn by_path(a: &Meta, b: &Meta) -> Ordering {
+    a.path.cmp(&b.path)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::dal::{Uri, DAL};
+    use crate::flags::sorting::SortColumns;
     use crate::flags::Flags;
+    use assert_fs::prelude::*;
+    use clap::Parser;
     use std::fs::{create_dir, File};
     use std::io::prelude::*;
+    use std::path::Path;
     use std::process::Command;
     use tempfile::tempdir;
 
-    #[test]
-    fn test_sort_assemble_sorters_by_name_with_dirs_first() {
+    #[tokio::test]
+    async fn test_sort_assemble_sorters_by_name_with_dirs_first() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
 
         // Create the file;
         let path_a = tmp_dir.path().join("zzz");
         File::create(&path_a).expect("failed to create file");
-        let meta_a = Meta::from_path(&path_a, false).expect("failed to get meta");
+        let meta_a = Meta::from_local(&path_a).await.expect("failed to get meta");
 
         // Create a dir;
         let path_z = tmp_dir.path().join("aaa");
         create_dir(&path_z).expect("failed to create dir");
-        let meta_z = Meta::from_path(&path_z, false).expect("failed to get meta");
+        let meta_z = Meta::from_local(&path_z).await.expect("failed to get meta");
 
         let mut flags = Flags::default();
         flags.sorting.dir_grouping = DirGrouping::First;
@@ -112,19 +139,19 @@ mod tests {
         assert_eq!(by_meta(&sorter, &meta_a, &meta_z), Ordering::Greater);
     }
 
-    #[test]
-    fn test_sort_assemble_sorters_by_name_with_files_first() {
+    #[tokio::test]
+    async fn test_sort_assemble_sorters_by_name_with_files_first() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
 
         // Create the file;
         let path_a = tmp_dir.path().join("zzz");
         File::create(&path_a).expect("failed to create file");
-        let meta_a = Meta::from_path(&path_a, false).expect("failed to get meta");
+        let meta_a = Meta::from_local(&path_a).await.expect("failed to get meta");
 
         // Create a dir;
         let path_z = tmp_dir.path().join("aaa");
         create_dir(&path_z).expect("failed to create dir");
-        let meta_z = Meta::from_path(&path_z, false).expect("failed to get meta");
+        let meta_z = Meta::from_local(&path_z).await.expect("failed to get meta");
 
         let mut flags = Flags::default();
         flags.sorting.dir_grouping = DirGrouping::Last;
@@ -138,19 +165,19 @@ mod tests {
         assert_eq!(by_meta(&sorter, &meta_a, &meta_z), Ordering::Less);
     }
 
-    #[test]
-    fn test_sort_assemble_sorters_by_name_unordered() {
+    #[tokio::test]
+    async fn test_sort_assemble_sorters_by_name_unordered() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
 
         // Create the file;
         let path_a = tmp_dir.path().join("aaa");
         File::create(&path_a).expect("failed to create file");
-        let meta_a = Meta::from_path(&path_a, false).expect("failed to get meta");
+        let meta_a = Meta::from_local(&path_a).await.expect("failed to get meta");
 
         // Create a dir;
         let path_z = tmp_dir.path().join("zzz");
         create_dir(&path_z).expect("failed to create dir");
-        let meta_z = Meta::from_path(&path_z, false).expect("failed to get meta");
+        let meta_z = Meta::from_local(&path_z).await.expect("failed to get meta");
 
         let mut flags = Flags::default();
         flags.sorting.dir_grouping = DirGrouping::None;
@@ -166,19 +193,19 @@ mod tests {
         assert_eq!(by_meta(&sorter, &meta_a, &meta_z), Ordering::Greater);
     }
 
-    #[test]
-    fn test_sort_assemble_sorters_by_name_unordered_2() {
+    #[tokio::test]
+    async fn test_sort_assemble_sorters_by_name_unordered_2() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
 
         // Create the file;
         let path_a = tmp_dir.path().join("zzz");
         File::create(&path_a).expect("failed to create file");
-        let meta_a = Meta::from_path(&path_a, false).expect("failed to get meta");
+        let meta_a = Meta::from_local(&path_a).await.expect("failed to get meta");
 
         // Create a dir;
         let path_z = tmp_dir.path().join("aaa");
         create_dir(&path_z).expect("failed to create dir");
-        let meta_z = Meta::from_path(&path_z, false).expect("failed to get meta");
+        let meta_z = Meta::from_local(&path_z).await.expect("failed to get meta");
 
         let mut flags = Flags::default();
         flags.sorting.dir_grouping = DirGrouping::None;
@@ -194,14 +221,14 @@ mod tests {
         assert_eq!(by_meta(&sorter, &meta_a, &meta_z), Ordering::Less);
     }
 
-    #[test]
-    fn test_sort_assemble_sorters_by_time() {
+    #[tokio::test]
+    async fn test_sort_assemble_sorters_by_time() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
 
         // Create the file;
         let path_a = tmp_dir.path().join("aaa");
         File::create(&path_a).expect("failed to create file");
-        let meta_a = Meta::from_path(&path_a, false).expect("failed to get meta");
+        let meta_a = Meta::from_local(&path_a).await.expect("failed to get meta");
 
         // Create the file;
         let path_z = tmp_dir.path().join("zzz");
@@ -227,10 +254,10 @@ mod tests {
             .success();
 
         assert!(success, "failed to change file timestamp");
-        let meta_z = Meta::from_path(&path_z, false).expect("failed to get meta");
+        let meta_z = Meta::from_local(&path_z).await.expect("failed to get meta");
 
         let mut flags = Flags::default();
-        flags.sorting.column = SortColumn::Time;
+        flags.sorting.columns = SortColumns(vec![SortColumn::Time.into()]);
 
         // Sort by time
         let sorter = assemble_sorters(&flags);
@@ -242,32 +269,32 @@ mod tests {
         assert_eq!(by_meta(&sorter, &meta_a, &meta_z), Ordering::Greater);
     }
 
-    #[test]
-    fn test_sort_assemble_sorters_by_extension() {
+    #[tokio::test]
+    async fn test_sort_assemble_sorters_by_extension() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
 
         // Create the file with rs extension;
         let path_a = tmp_dir.path().join("aaa.rs");
         File::create(&path_a).expect("failed to create file");
-        let meta_a = Meta::from_path(&path_a, false).expect("failed to get meta");
+        let meta_a = Meta::from_local(&path_a).await.expect("failed to get meta");
 
         // Create the file with rs extension;
         let path_z = tmp_dir.path().join("zzz.rs");
         File::create(&path_z).expect("failed to create file");
-        let meta_z = Meta::from_path(&path_z, false).expect("failed to get meta");
+        let meta_z = Meta::from_local(&path_z).await.expect("failed to get meta");
 
         // Create the file with js extension;
         let path_j = tmp_dir.path().join("zzz.js");
         File::create(&path_j).expect("failed to create file");
-        let meta_j = Meta::from_path(&path_j, false).expect("failed to get meta");
+        let meta_j = Meta::from_local(&path_j).await.expect("failed to get meta");
 
         // Create the file with txt extension;
         let path_t = tmp_dir.path().join("zzz.txt");
         File::create(&path_t).expect("failed to create file");
-        let meta_t = Meta::from_path(&path_t, false).expect("failed to get meta");
+        let meta_t = Meta::from_local(&path_t).await.expect("failed to get meta");
 
         let mut flags = Flags::default();
-        flags.sorting.column = SortColumn::Extension;
+        flags.sorting.columns = SortColumns(vec![SortColumn::Extension.into()]);
 
         // Sort by extension
         let sorter = assemble_sorters(&flags);
@@ -280,24 +307,24 @@ mod tests {
         assert_eq!(by_meta(&sorter, &meta_a, &meta_t), Ordering::Less);
     }
 
-    #[test]
-    fn test_sort_assemble_sorters_by_version() {
+    #[tokio::test]
+    async fn test_sort_assemble_sorters_by_version() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
 
         let path_a = tmp_dir.path().join("2");
         File::create(&path_a).expect("failed to create file");
-        let meta_a = Meta::from_path(&path_a, false).expect("failed to get meta");
+        let meta_a = Meta::from_local(&path_a).await.expect("failed to get meta");
 
         let path_b = tmp_dir.path().join("11");
         File::create(&path_b).expect("failed to create file");
-        let meta_b = Meta::from_path(&path_b, false).expect("failed to get meta");
+        let meta_b = Meta::from_local(&path_b).await.expect("failed to get meta");
 
         let path_c = tmp_dir.path().join("12");
         File::create(&path_c).expect("failed to create file");
-        let meta_c = Meta::from_path(&path_c, false).expect("failed to get meta");
+        let meta_c = Meta::from_local(&path_c).await.expect("failed to get meta");
 
         let mut flags = Flags::default();
-        flags.sorting.column = SortColumn::Version;
+        flags.sorting.columns = SortColumns(vec![SortColumn::Version.into()]);
 
         let sorter = assemble_sorters(&flags);
         assert_eq!(by_meta(&sorter, &meta_b, &meta_a), Ordering::Greater);
@@ -306,28 +333,28 @@ mod tests {
         assert_eq!(by_meta(&sorter, &meta_b, &meta_c), Ordering::Less);
     }
 
-    #[test]
-    fn test_sort_assemble_sorters_no_sort() {
+    #[tokio::test]
+    async fn test_sort_assemble_sorters_no_sort() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
 
         let path_a = tmp_dir.path().join("aaa.aa");
         File::create(&path_a).expect("failed to create file");
-        let meta_a = Meta::from_path(&path_a, false).expect("failed to get meta");
+        let meta_a = Meta::from_local(&path_a).await.expect("failed to get meta");
 
         let path_b = tmp_dir.path().join("aaa");
         create_dir(&path_b).expect("failed to create dir");
-        let meta_b = Meta::from_path(&path_b, false).expect("failed to get meta");
+        let meta_b = Meta::from_local(&path_b).await.expect("failed to get meta");
 
         let path_c = tmp_dir.path().join("zzz.zz");
         File::create(&path_c).expect("failed to create file");
-        let meta_c = Meta::from_path(&path_c, false).expect("failed to get meta");
+        let meta_c = Meta::from_local(&path_c).await.expect("failed to get meta");
 
         let path_d = tmp_dir.path().join("zzz");
         create_dir(&path_d).expect("failed to create dir");
-        let meta_d = Meta::from_path(&path_d, false).expect("failed to get meta");
+        let meta_d = Meta::from_local(&path_d).await.expect("failed to get meta");
 
         let mut flags = Flags::default();
-        flags.sorting.column = SortColumn::None;
+        flags.sorting.columns = SortColumns(vec![]);
 
         let sorter = assemble_sorters(&flags);
         assert_eq!(by_meta(&sorter, &meta_a, &meta_b), Ordering::Equal);
@@ -348,8 +375,8 @@ mod tests {
         assert_eq!(by_meta(&sorter, &meta_c, &meta_d), Ordering::Equal);
     }
 
-    #[test]
-    fn test_sort_by_size() {
+    #[tokio::test]
+    async fn test_sort_by_size() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
 
         let path_a = tmp_dir.path().join("aaa.aa");
@@ -357,29 +384,25 @@ mod tests {
             .expect("failed to create file")
             .write_all(b"1, 2, 3")
             .expect("failed to write to file");
-        let meta_a = Meta::from_path(&path_a, false).expect("failed to get meta");
+        let meta_a = Meta::from_local(&path_a).await.expect("failed to get meta");
 
         let path_b = tmp_dir.path().join("bbb.bb");
         File::create(&path_b)
             .expect("failed to create file")
             .write_all(b"1, 2, 3, 4, 5, 6, 7, 8, 9, 10")
             .expect("failed to write file");
-        let meta_b = Meta::from_path(&path_b, false).expect("failed to get meta");
-
-        let path_c = tmp_dir.path().join("ccc.cc");
-        let path_d = tmp_dir.path().join("ddd.dd");
-
-        #[cfg(unix)]
-        std::os::unix::fs::symlink(path_d, &path_c).expect("failed to create broken symlink");
-
-        // this needs to be tested on Windows
-        // likely to fail because of permission issue
-        // see https://doc.rust-lang.org/std/os/windows/fs/fn.symlink_file.html
-        #[cfg(windows)]
-        std::os::windows::fs::symlink_file(&path_d, &path_c)
-            .expect("failed to create broken symlink");
-
-        let meta_c = Meta::from_path(&path_c, true).expect("failed to get meta");
+        let meta_b = Meta::from_local(&path_b).await.expect("failed to get meta");
+
+        // Listed for their names only, the keys of an object store come without a size.
+        let uri = Uri::parse(Path::new("memory:///sort-by-size/"), &Config::with_none()).unwrap();
+        let dal = DAL::new(Path::new("/"), &uri, 1).unwrap();
+        dal.write(Path::new("sort-by-size/ccc.cc"), b"1".to_vec(), None)
+            .await
+            .unwrap();
+        let dir = dal.from_path(&uri.path).await.unwrap();
+        let (mut metas, _) = dal.recurse_into(&dir, 1, &Flags::default()).await.unwrap();
+        let meta_c = metas.remove(0);
+        assert!(meta_c.size().is_none());
 
         assert_eq!(by_size(&meta_a, &meta_a), Ordering::Equal);
         assert_eq!(by_size(&meta_a, &meta_b), Ordering::Greater);
@@ -393,4 +416,35 @@ mod tests {
         assert_eq!(by_size(&meta_c, &meta_b), Ordering::Less);
         assert_eq!(by_size(&meta_c, &meta_c), Ordering::Equal);
     }
+
+    #[tokio::test]
+    async fn test_sort_by_keys() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("b").write_str("0123456789").unwrap();
+        dir.child("a").write_str("9876543210").unwrap();
+        dir.child("c").write_str("01").unwrap();
+
+        let sorted = |sort: &'static str| {
+            let dir = dir.path().to_path_buf();
+            async move {
+                let cli = Cli::try_parse_from(["lsd", "--sort", sort]).unwrap();
+                let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+                let uri = Uri::parse(&dir, &Config::with_none()).unwrap();
+                let dal = DAL::new(Path::new("/"), &uri, 4).unwrap();
+                let meta = dal.from_path(&uri.path).await.unwrap();
+                let mut metas = dal.recurse_into(&meta, 1, &flags).await.unwrap().0;
+                let sorters = assemble_sorters(&flags);
+                metas.sort_by(|a, b| by_meta(&sorters, a, b));
+                metas
+                    .iter()
+                    .map(|meta| meta.name().file_name().to_string())
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        assert_eq!(vec!["a", "b", "c"], sorted("size,name").await);
+        assert_eq!(vec!["c", "a", "b"], sorted("-size,name").await);
+        assert_eq!(vec!["b", "a", "c"], sorted("size,-name").await);
+        assert_eq!(vec!["c", "b", "a"], sorted("-path").await);
+    }
 }
@@ -96,6 +96,7 @@ pub struct ColorTheme {
     #[serde(deserialize_with = "deserialize_color")]
     pub tree_edge: Color,
     pub links: Links,
+    pub metadata: Metadata,
 
     #[serde(skip)]
     pub file_type: FileType,
@@ -233,6 +234,21 @@ pub struct Links {
     pub invalid: Color,
 }
 
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+#[serde(deny_unknown_fields)]
+#[serde(default)]
+pub struct Metadata {
+    #[serde(deserialize_with = "deserialize_color")]
+    pub content_type: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub etag: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub content_md5: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub content_disposition: Color,
+}
+
 impl Default for Permission {
     fn default() -> Self {
         Permission {
@@ -323,6 +339,16 @@ impl Default for Links {
         }
     }
 }
+impl Default for Metadata {
+    fn default() -> Self {
+        Metadata {
+            content_type: Color::AnsiValue(110),        // LightSkyBlue3
+            etag: Color::AnsiValue(245),                // Grey
+            content_md5: Color::AnsiValue(245),         // Grey
+            content_disposition: Color::AnsiValue(187), // LightYellow3
+        }
+    }
+}
 
 impl Default for ColorTheme {
     fn default() -> Self {
@@ -342,6 +368,7 @@ impl ColorTheme {
             size: Size::default(),
             inode: INode::default(),
             links: Links::default(),
+            metadata: Metadata::default(),
             tree_edge: Color::AnsiValue(245), // Grey
         }
     }
@@ -377,6 +404,11 @@ inode:
 links:
   valid: 13
   invalid: 245
+metadata:
+  content-type: 110
+  etag: 245
+  content-md5: 245
+  content-disposition: 187
 tree-edge: 245
 "#
     }
@@ -0,0 +1,152 @@
+//! End-to-end tests of `dl`, listing the bucket of `tests/fixtures/bucket.yaml` seeded into the
+//! `memory://` service, without a disk or a network.
+//!
+//! Only the debug builds of `dl` can be seeded, the tests are left out of the release ones.
+#![cfg(debug_assertions)]
+
+extern crate assert_cmd;
+extern crate predicates;
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/bucket.yaml");
+
+/// `dl` seeded with the fixture, without the configuration file, colors or icons, and with the
+/// dates in UTC.
+fn cmd() -> Command {
+    let mut cmd = Command::cargo_bin("dl").expect("dl is built");
+    cmd.env("TZ", "UTC")
+        .arg("--ignore-config")
+        .args(["--seed", FIXTURE])
+        .args(["--color", "never", "--icon", "never"]);
+    cmd
+}
+
+#[test]
+fn test_list_root() {
+    cmd()
+        .args(["-1", "memory:///"])
+        .assert()
+        .success()
+        .stdout("assets\nempty\nlogs\nREADME.md\n");
+}
+
+#[test]
+fn test_long_sizes_and_dates() {
+    cmd()
+        .args(["-l", "--date", "+%Y-%m-%d", "--size", "bytes"])
+        .arg("memory:///logs/2026/")
+        .assert()
+        .success()
+        .stdout(concat!(
+            "3145728  [2026-01-02] app.log\n",
+            "   2048  [2026-01-03] error.log\n",
+        ));
+}
+
+#[test]
+fn test_sort_by_time() {
+    cmd()
+        .args(["-1", "-t", "memory:///logs/2026/"])
+        .assert()
+        .success()
+        .stdout("error.log\napp.log\n");
+}
+
+#[test]
+fn test_sort_by_size_reversed() {
+    cmd()
+        .args(["-1", "--sort", "-size", "memory:///logs/2026/"])
+        .assert()
+        .success()
+        .stdout("error.log\napp.log\n");
+}
+
+#[test]
+fn test_sort_by_content_type() {
+    cmd()
+        .args(["-1", "--sort", "content-type,-name", "memory:///assets/"])
+        .assert()
+        .success()
+        .stdout("logo.png\nstyle.css\n");
+}
+
+#[test]
+fn test_tree() {
+    cmd()
+        .args(["--tree", "memory:///logs/"])
+        .assert()
+        .success()
+        .stdout(
+            "logs\n\
+             ├── 2025\n\
+             │   └── app.log\n\
+             └── 2026\n    \
+             ├── app.log\n    \
+             └── error.log\n",
+        );
+}
+
+#[test]
+fn test_recursive_filters() {
+    cmd()
+        .args(["-1", "-R", "--larger", "2K", "memory:///logs/"])
+        .assert()
+        .success()
+        .stdout("2025\n2026\n\nlogs/2026/:\napp.log\n");
+}
+
+#[test]
+fn test_ndjson() {
+    cmd()
+        .args(["--format", "ndjson", "memory:///assets/"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""name":"logo.png""#))
+        .stdout(predicate::str::contains(r#""content_type":"image/png""#))
+        .stdout(predicate::str::contains(r#""etag":"9f6a2c""#));
+}
+
+#[test]
+fn test_du() {
+    cmd()
+        .args(["du", "-d", "1", "--size", "bytes", "memory:///"])
+        .assert()
+        .success()
+        .stdout(
+            "3200017  6  memory:///\n  \
+             51208  2  memory:///assets\n      \
+             0  0  memory:///empty\n\
+             3148800  3  memory:///logs\n",
+        );
+}
+
+#[test]
+fn test_cat() {
+    cmd()
+        .args(["cat", "memory:///README.md"])
+        .assert()
+        .success()
+        .stdout("# Bucket\n");
+}
+
+#[test]
+fn test_invalid_input_is_a_major_issue() {
+    cmd()
+        .args(["-1", "memory:///assets/", "unknown://host/"])
+        .assert()
+        .code(2)
+        .stdout("logo.png\nstyle.css\n")
+        .stderr(predicate::str::contains("unsupported scheme unknown"));
+}
+
+#[test]
+fn test_missing_fixture() {
+    Command::cargo_bin("dl")
+        .expect("dl is built")
+        .args(["--seed", "missing.yaml", "memory:///"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("missing.yaml"));
+}